@@ -10,6 +10,58 @@ pub struct VoteDetail {
     pub amount: u128,
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize, PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub struct VestingReward {
+    pub token_id: RewardTokenId,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    /// The amount already moved into rewards
+    #[serde(with = "u128_dec_format")]
+    pub released: Balance,
+    /// When the vesting starts in nanoseconds, that is the end_at of the proposal.
+    #[serde(with = "u64_dec_format")]
+    pub start_at: Timestamp,
+    pub duration_sec: u32,
+}
+
+impl VestingReward {
+    pub fn new(token_id: RewardTokenId, amount: Balance, start_at: Timestamp, duration_sec: u32) -> Self {
+        VestingReward {
+            token_id,
+            amount,
+            released: 0,
+            start_at,
+            duration_sec,
+        }
+    }
+
+    /// The amount vested linearly at given timestamp.
+    pub fn vested_amount(&self, timestamp: Timestamp) -> Balance {
+        let end_at = self.start_at + to_nano(self.duration_sec);
+        if timestamp >= end_at {
+            self.amount
+        } else if timestamp <= self.start_at {
+            0
+        } else {
+            u128_ratio(self.amount, (timestamp - self.start_at) as u128, (end_at - self.start_at) as u128)
+        }
+    }
+}
+
+/// Aggregate of the votes moved into history, kept even when the history is pruned or archived.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize, PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub struct HistoryStats {
+    /// Votes on expired proposals which have been claimed
+    pub votes_cast: u32,
+    /// Rewards claimed from those votes, including vesting rewards
+    #[serde(with = "u128_map_format")]
+    pub rewards_earned: HashMap<RewardTokenId, Balance>,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Serialize)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 #[serde(crate = "near_sdk::serde")]
@@ -32,18 +84,39 @@ pub struct Account {
     /// Record expired proposal voting info
     #[serde(skip_serializing)]
     pub proposals_history: UnorderedMap<u32, VoteDetail>,
+    pub history_stats: HistoryStats,
+    /// Rewards can be withdrawn, including the vested part of vesting_rewards once released.
     #[serde(with = "u128_map_format")]
-    pub rewards: HashMap<AccountId, Balance>,
+    pub rewards: HashMap<RewardTokenId, Balance>,
+    /// Rewards still vesting
+    pub vesting_rewards: Vec<VestingReward>,
+    /// Where withdrawn rewards go, None means the account itself.
+    pub reward_receiver: Option<AccountId>,
+    /// Keepers can't claim for the account if set.
+    pub keeper_claim_disabled: bool,
+    /// NEAR deposited for the storage of the account.
+    #[serde(with = "u128_dec_format")]
+    pub storage_balance: Balance,
+    /// Bytes the account record and vote history have grown beyond the baseline,
+    /// that is the size of the record at registration or migration.
+    pub storage_usage: StorageUsage,
+    /// The growth of the record on the next save is part of the baseline,
+    /// set for a new account or one migrated from V0200.
+    #[borsh_skip]
+    #[serde(skip_serializing)]
+    pub is_baseline_pending: bool,
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
 pub enum VAccount {
+    V0200(AccountV0200),
     Current(Account),
 }
 
 impl From<VAccount> for Account {
     fn from(v: VAccount) -> Self {
         match v {
+            VAccount::V0200(c) => c.into(),
             VAccount::Current(c) => c,
         }
     }
@@ -65,11 +138,47 @@ impl Account {
             duration_sec: 0,
             proposals: HashMap::new(),
             proposals_history: UnorderedMap::new(StorageKeys::AccountProposalHistory { account_id: account_id.clone() }),
-            rewards: HashMap::new()
+            history_stats: HistoryStats::default(),
+            rewards: HashMap::new(),
+            vesting_rewards: vec![],
+            reward_receiver: None,
+            keeper_claim_disabled: false,
+            storage_balance: 0,
+            storage_usage: 0,
+            is_baseline_pending: true,
+        }
+    }
+
+    /// NEAR locked for the storage, the growth of accounts registered by the contract itself is paid by the contract.
+    /// The registration fee covers the first bytes of growth it pays for.
+    pub fn storage_locked(&self) -> Balance {
+        if self.sponsor_id == env::current_account_id() {
+            0
+        } else {
+            std::cmp::max(STORAGE_BALANCE_MIN_BOUND, self.storage_usage as Balance * env::storage_byte_cost())
+        }
+    }
+
+    pub fn is_storage_covered(&self) -> bool {
+        self.storage_locked() <= self.storage_balance
+    }
+
+    /// Counts the contract storage change since prev_storage into storage_usage,
+    /// prev_storage must be taken right before writing the account's own data.
+    pub fn count_storage_since(&mut self, prev_storage: StorageUsage) {
+        let storage = env::storage_usage();
+        if storage >= prev_storage {
+            self.storage_usage += storage - prev_storage;
+        } else {
+            self.storage_usage = self.storage_usage.saturating_sub(prev_storage - storage);
         }
     }
 
-    pub fn add_rewards(&mut self, rewards: &HashMap<AccountId, Balance>) {
+    pub fn storage_available(&self) -> Balance {
+        self.storage_balance.saturating_sub(self.storage_locked())
+    }
+
+    pub fn add_rewards(&mut self, rewards: &HashMap<RewardTokenId, Balance>) {
         for (reward_token, reward) in rewards {
             self.rewards.insert(
                 reward_token.clone(),
@@ -78,20 +187,97 @@ impl Account {
         }
     }
 
-    pub fn add_history(&mut self, history: &HashMap<u32, VoteDetail>){
-        for (proposal_id, vote_detail) in history {
-            self.proposals_history.insert(proposal_id, vote_detail);
+    pub fn add_vesting_rewards(&mut self, vesting_rewards: Vec<VestingReward>) {
+        for vesting_reward in vesting_rewards {
+            if vesting_reward.amount == 0 {
+                continue;
+            }
+            // rewards vesting on the same schedule are merged
+            if let Some(item) = self.vesting_rewards.iter_mut().find(|item| {
+                item.token_id == vesting_reward.token_id 
+                    && item.start_at == vesting_reward.start_at 
+                    && item.duration_sec == vesting_reward.duration_sec
+            }) {
+                item.amount += vesting_reward.amount;
+            } else {
+                self.vesting_rewards.push(vesting_reward);
+            }
         }
     }
 
-    pub fn sub_reward(&mut self, token_id: &AccountId, amount: Balance) {
-        if let Some(prev) = self.rewards.remove(token_id) {
-            require!(amount <= prev, E101_INSUFFICIENT_BALANCE);
-            let remain = prev - amount;
-            if remain > 0 {
-                self.rewards.insert(token_id.clone(), remain);
+    /// Moves the vested part of vesting_rewards into rewards.
+    pub fn release_vested_rewards(&mut self) {
+        let timestamp = env::block_timestamp();
+        let mut rewards = HashMap::new();
+        for item in self.vesting_rewards.iter_mut() {
+            let vested = item.vested_amount(timestamp);
+            if vested > item.released {
+                rewards.insert(item.token_id.clone(), vested - item.released + rewards.get(&item.token_id).unwrap_or(&0_u128));
+                item.released = vested;
             }
         }
+        self.vesting_rewards.retain(|item| item.released < item.amount);
+        self.add_rewards(&rewards);
+    }
+
+    /// The view of the account, with vested rewards released.
+    pub fn to_info(mut self, account_id: AccountId) -> AccountInfo {
+        self.release_vested_rewards();
+        AccountInfo {
+            account_id,
+            sponsor_id: self.sponsor_id,
+            lpt_amount: self.lpt_amount,
+            ve_lpt_amount: self.ve_lpt_amount,
+            unlock_timestamp: self.unlock_timestamp,
+            duration_sec: self.duration_sec,
+            rewards: self.rewards,
+            vesting_rewards: self.vesting_rewards,
+            reward_receiver: self.reward_receiver,
+            keeper_claim_disabled: self.keeper_claim_disabled,
+        }
+    }
+
+    /// Counts the claimed votes and their rewards into history_stats,
+    /// the votes themselves are not kept in archival mode,
+    /// or when the storage balance can't cover them.
+    pub fn add_history(
+        &mut self, 
+        history: &HashMap<u32, VoteDetail>, 
+        rewards: &HashMap<RewardTokenId, Balance>, 
+        vesting_rewards: &[VestingReward], 
+        archive: bool
+    ){
+        self.history_stats.votes_cast += history.len() as u32;
+        let rewards_earned = rewards.iter().map(|(token_id, amount)| (token_id, *amount))
+            .chain(vesting_rewards.iter().map(|item| (&item.token_id, item.amount)));
+        for (token_id, amount) in rewards_earned {
+            if amount > 0 {
+                let prev = self.history_stats.rewards_earned.get(token_id).unwrap_or(&0_u128);
+                self.history_stats.rewards_earned.insert(token_id.clone(), prev + amount);
+            }
+        }
+        if !archive {
+            for (proposal_id, vote_detail) in history {
+                let prev_storage = env::storage_usage();
+                self.proposals_history.insert(proposal_id, vote_detail);
+                self.count_storage_since(prev_storage);
+                if !self.is_storage_covered() {
+                    let prev_storage = env::storage_usage();
+                    self.proposals_history.remove(proposal_id);
+                    self.count_storage_since(prev_storage);
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn sub_reward(&mut self, token_id: &RewardTokenId, amount: Balance) {
+        let prev = self.rewards.remove(token_id).unwrap_or(0);
+        require!(amount <= prev, E101_INSUFFICIENT_BALANCE);
+        let remain = prev - amount;
+        if remain > 0 {
+            self.rewards.insert(token_id.clone(), remain);
+        }
     }
 
     pub fn lock_lpt(&mut self, amount: Balance, duration_sec: u32, config: &Config, lptoken_decimals: u8) -> Balance {
@@ -141,19 +327,27 @@ impl Account {
 
 impl Contract {
     pub fn update_impacted_proposals(&mut self, account: &mut Account, diff_ve_lpt_amount: Balance, is_increased: bool){
+        let archive = self.data().archive_history;
         let mut rewards = HashMap::new();
+        let mut vesting_rewards = vec![];
         let mut history = HashMap::new();
         account.proposals.retain(|proposal_id, vote_detail| {
             let mut proposal = self.internal_unwrap_proposal(*proposal_id);
             if proposal.status == Some(ProposalStatus::Expired) {
-                if let Some(reward_details) = proposal.claim_reward(vote_detail) {
+                if let Some((reward_details, vesting_details)) = proposal.claim_reward(vote_detail) {
                     reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
                         rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
                     });
+                    vesting_rewards.extend(vesting_details);
                 }
                 self.internal_set_proposal(*proposal_id, proposal.into());
                 history.insert(*proposal_id, vote_detail.clone());
                 false
+            } else if proposal.status == Some(ProposalStatus::Cancelled) {
+                // released votes of a cancelled proposal are counted as claimed
+                proposal.claimed_count += 1;
+                self.internal_set_proposal(*proposal_id, proposal);
+                false
             } else {
                 let mut is_retain = true;
                 if diff_ve_lpt_amount > 0 {
@@ -173,8 +367,9 @@ impl Contract {
                 is_retain
             }
         });
+        account.add_history(&history, &rewards, &vesting_rewards, archive);
         account.add_rewards(&rewards);
-        account.add_history(&history);
+        account.add_vesting_rewards(vesting_rewards);
     }
 
     pub fn internal_account_vote(
@@ -220,7 +415,32 @@ impl Contract {
             .expect(E100_ACC_NOT_REGISTERED)
     }
 
+    /// Counts the size change of the account record into the account,
+    /// the growth caused by the account's own call must be covered by its storage balance.
     pub fn internal_set_account(&mut self, account_id: &AccountId, account: Account) {
+        self.internal_save_account(account_id, account, true);
+    }
+
+    /// Same as internal_set_account but never fails for storage,
+    /// for calls that must not be blocked by it, such as withdraw_lpt.
+    pub fn internal_set_account_unchecked(&mut self, account_id: &AccountId, account: Account) {
+        self.internal_save_account(account_id, account, false);
+    }
+
+    fn internal_save_account(&mut self, account_id: &AccountId, account: Account, check_storage: bool) {
+        let prev_storage = env::storage_usage();
+        let v_account: VAccount = account.into();
+        self.data_mut().accounts.insert(account_id, &v_account);
+        let mut account: Account = v_account.into();
+        if account.is_baseline_pending || env::storage_usage() == prev_storage {
+            return;
+        }
+        let prev_storage_usage = account.storage_usage;
+        account.count_storage_since(prev_storage);
+        if check_storage && account.storage_usage > prev_storage_usage && &env::predecessor_account_id() == account_id {
+            require!(account.is_storage_covered(), E102_INSUFFICIENT_STORAGE);
+        }
+        // storage_usage is of fixed size, the record doesn't change its size
         self.data_mut().accounts.insert(account_id, &account.into());
     }
 
@@ -229,6 +449,7 @@ impl Contract {
             account
         } else {
             self.data_mut().account_count += 1;
+            self.data_mut().account_index.insert(account_id);
             Account::new(account_id, &env::current_account_id())
         }
     }
@@ -237,6 +458,7 @@ impl Contract {
         self.ft.accounts.remove(account_id);
         self.data_mut().accounts.remove(account_id);
         self.data_mut().account_count -= 1;
+        self.data_mut().account_index.remove(account_id);
     }
 }
 
@@ -22,11 +22,57 @@ impl Contract {
 
         self.update_impacted_proposals(&mut account, decreased_ve_lpt, false);
         
-        self.internal_set_account(&account_id, account);
+        // lpt is never held back by storage
+        self.internal_set_account_unchecked(&account_id, account);
+
+        self.transfer_lpt_token(&account_id, amount)
+    }
+
+    /// Resends the caller's lpt which failed to withdraw.
+    #[payable]
+    pub fn retry_withdraw_lpt(&mut self) -> Promise {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let amount = self.data().lpt_lostfound.get(&account_id).unwrap_or(0);
+        require!(amount > 0, E101_INSUFFICIENT_BALANCE);
+        self.internal_sub_lpt_lostfound(&account_id, amount);
 
         self.transfer_lpt_token(&account_id, amount)
     }
 
+    /// Removes the caller's vote history before before_proposal_id among at most limit entries from from_index,
+    /// in list_vote_detail_history order, the storage released goes back to the available storage balance.
+    /// history_stats are kept. Returns the number of removed entries.
+    #[payable]
+    pub fn prune_proposal_history(&mut self, before_proposal_id: u32, from_index: Option<u64>, limit: Option<u64>) -> u32 {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        let keys = account.proposals_history.keys_as_vector();
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(DEFAULT_HISTORY_BATCH_LIMIT);
+        let proposal_ids: Vec<u32> = (from_index..std::cmp::min(keys.len(), from_index.saturating_add(limit)))
+            .map(|index| keys.get(index).unwrap())
+            .filter(|proposal_id| *proposal_id < before_proposal_id)
+            .collect();
+        for proposal_id in proposal_ids.iter() {
+            let prev_storage = env::storage_usage();
+            account.proposals_history.remove(proposal_id);
+            account.count_storage_since(prev_storage);
+        }
+        self.internal_set_account(&account_id, account);
+
+        let pruned_count = proposal_ids.len() as u32;
+        Event::ProposalHistoryPrune {
+            caller_id: &account_id,
+            before_proposal_id,
+            pruned_count,
+        }
+        .emit();
+        pruned_count
+    }
 
     #[private]
     pub fn callback_withdraw_lpt(&mut self, sender_id: AccountId, amount: U128) {
@@ -39,7 +85,7 @@ impl Contract {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Failed => {
                 // all token amount go to lostfound
-                self.data_mut().lostfound += amount;
+                self.internal_add_lpt_lostfound(&sender_id, amount);
 
                 Event::LptWithdraw {
                     caller_id: &sender_id,
@@ -61,6 +107,24 @@ impl Contract {
 }
 
 impl Contract {
+    pub fn internal_add_lpt_lostfound(&mut self, account_id: &AccountId, amount: Balance) {
+        self.data_mut().lostfound += amount;
+        let current_amount = self.data().lpt_lostfound.get(account_id).unwrap_or(0);
+        self.data_mut().lpt_lostfound.insert(account_id, &(current_amount + amount));
+    }
+
+    /// The record of account_id is reduced as much as it has, the rest comes from unrecorded lostfound.
+    pub fn internal_sub_lpt_lostfound(&mut self, account_id: &AccountId, amount: Balance) {
+        require!(amount <= self.data().lostfound, E101_INSUFFICIENT_BALANCE);
+        self.data_mut().lostfound -= amount;
+        let current_amount = self.data().lpt_lostfound.get(account_id).unwrap_or(0);
+        if current_amount > amount {
+            self.data_mut().lpt_lostfound.insert(account_id, &(current_amount - amount));
+        } else if current_amount > 0 {
+            self.data_mut().lpt_lostfound.remove(account_id);
+        }
+    }
+
     fn transfer_lpt_token(
         &self,
         account_id: &AccountId,
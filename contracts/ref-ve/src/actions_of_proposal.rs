@@ -2,6 +2,8 @@ use crate::*;
 
 #[near_bindgen]
 impl Contract {
+    /// The proposer pays for the storage of the proposal, the rest of the attached deposit is refunded.
+    /// metadata points to the full text off-chain, tags categorize the proposal for listing.
     #[payable]
     pub fn create_proposal(
         &mut self,
@@ -9,61 +11,34 @@ impl Contract {
         description: String,
         start_at: u32,
         duration_sec: u32,
+        metadata: Option<ProposalMetadata>,
+        tags: Option<Vec<String>>,
     ) -> u32 {
-        assert_one_yocto();
-
+        let prev_storage = env::storage_usage();
         let proposer = env::predecessor_account_id();
-        require!(self.data().whitelisted_accounts.contains(&proposer) , E002_NOT_ALLOWED);
-        require!(description.len() <= DESCRIPTION_LIMIT , E208_DESCRIPTION_TOO_LONG);
-        
-        self.internal_unwrap_account(&proposer);
-
-        let config = self.internal_config();
-
-        require!(duration_sec >= config.min_voting_duration_sec && duration_sec <= config.max_voting_duration_sec
-            , E302_INVALID_DURATION);
-        require!(start_at - nano_to_sec(env::block_timestamp()) >= config.min_proposal_start_vote_offset_sec, E402_INVALID_START_TIME);
-
-        let votes: Vec<VoteInfo> = match &kind {
-            ProposalKind::FarmingReward{ farm_list, .. } => {
-                vec![Default::default(); farm_list.len()]
-            },
-            ProposalKind::Poll{ options, .. } => {
-                vec![Default::default(); options.len()]
-            },
-            ProposalKind::Common{ .. } => {
-                vec![Default::default(); 3]
-            }
-        };
+        let proposal_id = self.internal_create_proposal(&proposer, kind.clone(), description, metadata, tags.unwrap_or_default(), start_at, duration_sec);
 
-        let id = self.data().last_proposal_id;
-        let proposal = Proposal{
-            id,
-            description,
-            proposer: proposer.clone(),
-            kind: kind.clone(),
-            votes,
-            ve_amount_at_last_action: self.data().cur_total_ve_lpt,
-            incentive: HashMap::new(),
-            start_at: to_nano(start_at),
-            end_at: to_nano(start_at + duration_sec),
-            participants: 0,
-            status: None,
-            is_nonsense: None
-        };
-        self.internal_set_proposal(id, proposal.into());
+        let storage_deposit = (env::storage_usage() - prev_storage) as Balance * env::storage_byte_cost();
+        let attached_deposit = env::attached_deposit();
+        require!(attached_deposit >= storage_deposit, E102_INSUFFICIENT_STORAGE);
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        proposal.storage_deposit = storage_deposit;
+        self.internal_set_proposal(proposal_id, proposal);
 
         Event::ProposalCreate {
             proposer_id: &proposer,
-            proposal_id: id,
+            proposal_id,
             kind: &format!("{:?}", kind),
             start_at: to_nano(start_at),
             duration_sec
         }
         .emit();
-        
-        self.data_mut().last_proposal_id += 1;
-        id
+
+        let refund = attached_deposit - storage_deposit;
+        if refund > 0 {
+            Promise::new(proposer).transfer(refund);
+        }
+        proposal_id
     }
 
     #[payable]
@@ -75,18 +50,14 @@ impl Contract {
         let proposal = self.internal_unwrap_proposal(proposal_id);
         require!(proposal.proposer == proposer, E002_NOT_ALLOWED);
 
-        match proposal.status.unwrap() {
+        match proposal.status.clone().unwrap() {
             ProposalStatus::WarmUp => {
                 self.data_mut().proposals.remove(&proposal_id);
+                self.internal_unindex_proposal(&proposal);
 
-                for item in proposal.incentive.values() {
-                    for index in 0..item.incentive_token_ids.len() {
-                        let current_amount = self.data().removed_proposal_assets.get(&item.incentive_token_ids[index]).unwrap_or(0_u128);
-                        self.data_mut().removed_proposal_assets.insert(
-                            &item.incentive_token_ids[index],
-                            &(item.incentive_amounts[index] + current_amount),
-                        );
-                    }
+                self.internal_refund_removed_incentive(&proposal);
+                if proposal.storage_deposit > 0 {
+                    Promise::new(proposer.clone()).transfer(proposal.storage_deposit);
                 }
 
                 Event::ProposalRemove {
@@ -101,6 +72,126 @@ impl Contract {
         }
     }
 
+    /// Guardians could cancel a proposal no voter has claimed yet, which fails with E415 otherwise,
+    /// the incentives are credited back to their depositors,
+    /// voters are released from the proposal without rewards on their next action.
+    /// It could be archived once every voter is released.
+    #[payable]
+    pub fn cancel_proposal(&mut self, proposal_id: u32, reason: String) {
+        assert_one_yocto();
+        let guardian_id = env::predecessor_account_id();
+        require!(self.data().guardians.contains(&guardian_id), E002_NOT_ALLOWED);
+        require!(reason.len() <= DESCRIPTION_LIMIT, E208_DESCRIPTION_TOO_LONG);
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        require!(proposal.status != Some(ProposalStatus::Cancelled), E414_PROPOSAL_CANCELLED);
+        require!(proposal.claimed_count == 0 && !proposal.has_untracked_claims, E415_PROPOSAL_CLAIMED);
+
+        for (incentive_key, incentive) in proposal.incentive.drain() {
+            self.internal_refund_incentive((proposal_id, incentive_key), &incentive);
+        }
+        proposal.cancellation = Some(Cancellation {
+            guardian_id: guardian_id.clone(),
+            reason,
+            cancelled_at: env::block_timestamp(),
+        });
+
+        Event::ProposalCancel {
+            guardian_id: &guardian_id,
+            proposal_id,
+            reason: &proposal.cancellation.as_ref().unwrap().reason,
+        }
+        .emit();
+        self.internal_set_proposal(proposal_id, proposal);
+    }
+
+    /// Proposer could edit the proposal before voting begins, options replace the options of a Poll
+    /// or the farm_list of a FarmingReward. Incentives on the options changed are credited back to
+    /// their depositors, the others are kept. Attach at least 1 yocto plus the storage increase,
+    /// including the incentive refunds credited, the rest is refunded. Returns the revision of the proposal.
+    #[payable]
+    pub fn update_proposal(
+        &mut self,
+        proposal_id: u32,
+        description: Option<String>,
+        options: Option<Vec<String>>,
+        start_at: Option<u32>,
+        duration_sec: Option<u32>,
+    ) -> u32 {
+        let proposer = env::predecessor_account_id();
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        require!(proposal.proposer == proposer, E002_NOT_ALLOWED);
+        require!(proposal.status == Some(ProposalStatus::WarmUp), E412_PROPOSAL_NOT_WARMUP);
+        require!(self.data().whitelisted_accounts.contains(&proposer), E002_NOT_ALLOWED);
+        let prev_storage = env::storage_usage();
+
+        if let Some(description) = description {
+            require!(description.len() <= DESCRIPTION_LIMIT, E208_DESCRIPTION_TOO_LONG);
+            proposal.description = description;
+        }
+
+        let config = self.internal_config();
+        let duration_sec = duration_sec.unwrap_or_else(|| nano_to_sec(proposal.end_at - proposal.start_at));
+        require!(duration_sec >= config.min_voting_duration_sec && duration_sec <= config.max_voting_duration_sec
+            , E302_INVALID_DURATION);
+        if let Some(start_at) = start_at {
+            require!(matches!(start_at.checked_sub(nano_to_sec(env::block_timestamp())), Some(offset) if offset >= config.min_proposal_start_vote_offset_sec), E402_INVALID_START_TIME);
+            proposal.start_at = to_nano(start_at);
+        }
+        proposal.end_at = proposal.start_at + to_nano(duration_sec);
+
+        if let Some(options) = options {
+            self.internal_update_proposal_options(&mut proposal, options);
+        }
+
+        proposal.revision += 1;
+        // storage_deposit is of fixed size, so the delta is final once the proposal is saved
+        self.internal_set_proposal(proposal_id, proposal);
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        let storage = env::storage_usage();
+        let attached_deposit = env::attached_deposit();
+        let refund = if storage > prev_storage {
+            let storage_cost = (storage - prev_storage) as Balance * env::storage_byte_cost();
+            require!(attached_deposit >= std::cmp::max(storage_cost, 1), E102_INSUFFICIENT_STORAGE);
+            proposal.storage_deposit += storage_cost;
+            attached_deposit - storage_cost
+        } else {
+            require!(attached_deposit >= 1, E102_INSUFFICIENT_STORAGE);
+            let released = std::cmp::min((prev_storage - storage) as Balance * env::storage_byte_cost(), proposal.storage_deposit);
+            proposal.storage_deposit -= released;
+            attached_deposit + released
+        };
+        if refund > 0 {
+            Promise::new(proposer.clone()).transfer(refund);
+        }
+
+        let revision = proposal.revision;
+        Event::ProposalUpdate {
+            proposer_id: &proposer,
+            proposal_id,
+            revision,
+            start_at: proposal.start_at,
+            duration_sec,
+        }
+        .emit();
+        self.internal_set_proposal(proposal_id, proposal);
+        revision
+    }
+
+    /// Anyone can archive an expired proposal once every voter has claimed and its dust,
+    /// if any, is swept, or a cancelled one once every voter is released,
+    /// only its summary is kept to release the storage.
+    pub fn archive_proposal(&mut self, proposal_id: u32) -> ProposalSummary {
+        let summary = self.internal_archive_proposal(proposal_id);
+
+        Event::ProposalArchive {
+            proposal_id,
+            content_hash: &summary.content_hash,
+        }
+        .emit();
+
+        summary
+    }
+
     #[payable]
     pub fn action_proposal(&mut self, proposal_id: u32, action: Action, memo: Option<String>) -> U128 {
         assert_one_yocto();
@@ -143,4 +234,115 @@ impl Contract {
 
         vote_detail.amount.into()
     }
+
+    /// Anyone can materialize the due rounds of proposal schedules, visiting at most limit
+    /// schedules from from_index in list_proposal_schedules order. Returns ids of the created proposals.
+    pub fn materialize_scheduled_proposals(&mut self, from_index: Option<u64>, limit: Option<u64>) -> Vec<u32> {
+        self.internal_materialize_scheduled_proposals(
+            from_index.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_SCHEDULE_BATCH_LIMIT)
+        )
+    }
+}
+
+impl Contract {
+    /// Incentives stay with the options unchanged at the same index, incentive 0 of a Poll
+    /// rewards all voters so it's always kept.
+    fn internal_update_proposal_options(&mut self, proposal: &mut Proposal, options: Vec<String>) {
+        let (prev_options, key_offset) = match &mut proposal.kind {
+            ProposalKind::FarmingReward { farm_list, .. } => (std::mem::replace(farm_list, options.clone()), 0),
+            ProposalKind::Poll { options: poll_options } => (std::mem::replace(poll_options, options.clone()), 1),
+            ProposalKind::Common => env::panic_str(E413_INVALID_OPTIONS),
+        };
+        proposal.votes = vec![Default::default(); options.len()];
+
+        let invalid_keys: Vec<u32> = proposal.incentive.keys()
+            .filter(|incentive_key| {
+                if **incentive_key < key_offset {
+                    return false;
+                }
+                let index = (**incentive_key - key_offset) as usize;
+                index >= options.len() || prev_options.get(index) != Some(&options[index])
+            })
+            .cloned()
+            .collect();
+        for incentive_key in invalid_keys {
+            let incentive = proposal.incentive.remove(&incentive_key).unwrap();
+            self.internal_refund_incentive((proposal.id, incentive_key), &incentive);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn internal_create_proposal(
+        &mut self,
+        proposer: &AccountId,
+        kind: ProposalKind,
+        description: String,
+        metadata: Option<ProposalMetadata>,
+        tags: Vec<String>,
+        start_at: u32,
+        duration_sec: u32,
+    ) -> u32 {
+        require!(self.data().whitelisted_accounts.contains(proposer) , E002_NOT_ALLOWED);
+        require!(description.len() <= DESCRIPTION_LIMIT , E208_DESCRIPTION_TOO_LONG);
+        let metadata = metadata.map(|metadata| metadata.validate());
+        let tags = validate_tags(tags);
+        
+        self.internal_unwrap_account(proposer);
+
+        let config = self.internal_config();
+
+        require!(duration_sec >= config.min_voting_duration_sec && duration_sec <= config.max_voting_duration_sec
+            , E302_INVALID_DURATION);
+        require!(matches!(start_at.checked_sub(nano_to_sec(env::block_timestamp())), Some(offset) if offset >= config.min_proposal_start_vote_offset_sec), E402_INVALID_START_TIME);
+
+        let votes: Vec<VoteInfo> = match &kind {
+            ProposalKind::FarmingReward{ farm_list, .. } => {
+                vec![Default::default(); farm_list.len()]
+            },
+            ProposalKind::Poll{ options, .. } => {
+                vec![Default::default(); options.len()]
+            },
+            ProposalKind::Common{ .. } => {
+                vec![Default::default(); 3]
+            }
+        };
+
+        let id = self.data().last_proposal_id;
+        let proposal = Proposal{
+            id,
+            description,
+            metadata,
+            tags,
+            proposer: proposer.clone(),
+            kind: kind.clone(),
+            votes,
+            ve_amount_at_last_action: self.data().cur_total_ve_lpt,
+            incentive: HashMap::new(),
+            start_at: to_nano(start_at),
+            end_at: to_nano(start_at + duration_sec),
+            participants: 0,
+            claimed_count: 0,
+            has_untracked_claims: false,
+            is_settled: false,
+            storage_deposit: 0,
+            revision: 0,
+            cancellation: None,
+            status: None,
+            is_nonsense: None
+        };
+        self.internal_index_proposal(&proposal);
+        self.internal_set_proposal(id, proposal.into());
+        
+        self.data_mut().last_proposal_id += 1;
+        id
+    }
+
+    /// Undoes internal_create_proposal of the last created proposal.
+    pub fn internal_revert_last_proposal(&mut self, proposal_id: u32) {
+        let proposal = self.internal_unwrap_proposal(proposal_id);
+        self.data_mut().proposals.remove(&proposal_id);
+        self.internal_unindex_proposal(&proposal);
+        self.data_mut().last_proposal_id -= 1;
+    }
 }
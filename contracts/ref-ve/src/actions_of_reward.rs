@@ -1,7 +1,39 @@
 use std::iter::FromIterator;
 
 use crate::*;
-use near_contract_standards::fungible_token::core_impl::ext_fungible_token;
+use near_contract_standards::storage_management::{StorageBalance, StorageBalanceBounds};
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct ClaimRewardsResult {
+    /// Proposals claimed by this call, the others are not expired or not voted
+    pub claimed_proposal_ids: Vec<u32>,
+    #[serde(with = "u128_map_format")]
+    pub rewards: HashMap<RewardTokenId, Balance>,
+    pub vesting_rewards: Vec<VestingReward>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct WithdrawRewardsResult {
+    #[serde(with = "u128_map_format")]
+    pub withdrawn: HashMap<RewardTokenId, Balance>,
+    /// Tokens left for the next call as prepaid gas ran out
+    pub remaining_token_ids: Vec<RewardTokenId>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct KeeperClaimResult {
+    /// Accounts claimed by this call, the others are not registered, opted out or have nothing to claim
+    pub claimed_account_ids: Vec<AccountId>,
+    /// Fees credited to the keeper's rewards
+    #[serde(with = "u128_map_format")]
+    pub fees: HashMap<RewardTokenId, Balance>,
+}
 
 #[near_bindgen]
 impl Contract {
@@ -10,34 +42,155 @@ impl Contract {
         let account_id = env::predecessor_account_id();
         let mut account = self.internal_unwrap_account(&account_id);
         self.internal_claim_all(&mut account);
+        account.release_vested_rewards();
+        let receiver_id = account.reward_receiver.clone().unwrap_or_else(|| account_id.clone());
         account.rewards.retain(|token_id, amount|{
-            self.transfer_reward(token_id, &account_id, *amount);
+            self.transfer_reward(token_id, &account_id, &receiver_id, *amount);
             false
         });
         self.internal_set_account(&account_id, account);
     }
 
+    /// Anyone registered can claim expired proposals for given accounts,
+    /// keeper_fee_bps of the claimed rewards goes to the keeper's rewards, vesting rewards are not charged.
+    pub fn keeper_claim(&mut self, account_ids: Vec<AccountId>) -> KeeperClaimResult {
+        let keeper_id = env::predecessor_account_id();
+        self.internal_unwrap_account(&keeper_id);
+        let keeper_fee_bps = self.data().keeper_fee_bps;
+        let mut result = KeeperClaimResult {
+            claimed_account_ids: vec![],
+            fees: HashMap::new(),
+        };
+        for account_id in account_ids {
+            let mut account = match self.internal_get_account(&account_id) {
+                Some(account) if !account.keeper_claim_disabled => account,
+                _ => continue,
+            };
+            let prev_count = account.proposals.len();
+            let rewards = self.internal_claim_all(&mut account);
+            if account.proposals.len() == prev_count {
+                continue;
+            }
+            for (token_id, amount) in rewards {
+                let fee = u128_ratio(amount, keeper_fee_bps as u128, FEE_DIVISOR as u128);
+                if fee > 0 {
+                    account.sub_reward(&token_id, fee);
+                    *result.fees.entry(token_id).or_insert(0) += fee;
+                }
+            }
+            self.internal_set_account(&account_id, account);
+            result.claimed_account_ids.push(account_id);
+        }
+        if !result.fees.is_empty() {
+            let mut keeper = self.internal_unwrap_account(&keeper_id);
+            keeper.add_rewards(&result.fees);
+            self.internal_set_account(&keeper_id, keeper);
+        }
+        result
+    }
+
+    /// Keepers can't claim for the caller once disabled.
+    #[payable]
+    pub fn set_keeper_claim_disabled(&mut self, disabled: bool) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        account.keeper_claim_disabled = disabled;
+        self.internal_set_account(&account_id, account);
+    }
+
     pub fn claim_reward(&mut self, proposal_id: u32) {
         let account_id = env::predecessor_account_id();
         let mut account = self.internal_unwrap_account(&account_id);
-        let mut proposal = self.internal_unwrap_proposal(proposal_id);
-        if proposal.status == Some(ProposalStatus::Expired) {
-            if let Some(vote_detail) = account.proposals.remove(&proposal_id) {
-                if let Some(reward_details) = proposal.claim_reward(&vote_detail) {
-                    account.add_rewards(&HashMap::from_iter(reward_details));
+        if let Some((reward_details, vesting_details)) = self.internal_claim_reward(&mut account, proposal_id) {
+            account.add_rewards(&HashMap::from_iter(reward_details));
+            account.add_vesting_rewards(vesting_details);
+            self.internal_set_account(&account_id, account);
+        }
+    }
+
+    /// Claims given proposals, those not expired or not voted are skipped.
+    pub fn claim_rewards(&mut self, proposal_ids: Vec<u32>) -> ClaimRewardsResult {
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        let mut result = ClaimRewardsResult {
+            claimed_proposal_ids: vec![],
+            rewards: HashMap::new(),
+            vesting_rewards: vec![],
+        };
+        for proposal_id in proposal_ids {
+            if let Some((reward_details, vesting_details)) = self.internal_claim_reward(&mut account, proposal_id) {
+                for (token_id, amount) in reward_details {
+                    *result.rewards.entry(token_id).or_insert(0) += amount;
                 }
-                self.internal_set_proposal(proposal_id, proposal.into());
-                account.proposals_history.insert(&proposal_id, &vote_detail);
-                self.internal_set_account(&account_id, account);
+                result.vesting_rewards.extend(vesting_details);
+                result.claimed_proposal_ids.push(proposal_id);
             }
         }
+        account.add_rewards(&result.rewards);
+        account.add_vesting_rewards(result.vesting_rewards.clone());
+        self.internal_set_account(&account_id, account);
+        result
+    }
+
+    /// Withdraws all balance of given reward tokens,
+    /// tokens beyond the prepaid gas are left to the next call.
+    pub fn withdraw_rewards(&mut self, token_ids: Vec<RewardTokenId>) -> WithdrawRewardsResult {
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        account.release_vested_rewards();
+        let receiver_id = account.reward_receiver.clone().unwrap_or_else(|| account_id.clone());
+        let gas_per_withdraw = GAS_FOR_REWARD_TRANSFER.0 + GAS_FOR_RESOLVE_REWARD_TRANSFER.0;
+        let mut result = WithdrawRewardsResult {
+            withdrawn: HashMap::new(),
+            remaining_token_ids: vec![],
+        };
+        let mut transfers = vec![];
+        for token_id in token_ids {
+            let amount = account.rewards.get(&token_id).cloned().unwrap_or(0);
+            if amount == 0 || result.withdrawn.contains_key(&token_id) {
+                continue;
+            }
+            if env::prepaid_gas().0 - env::used_gas().0 <= gas_per_withdraw * (transfers.len() as u64 + 1) + GAS_FOR_WITHDRAW_REWARDS_FINISH.0 {
+                result.remaining_token_ids.push(token_id);
+                continue;
+            }
+            account.sub_reward(&token_id, amount);
+            result.withdrawn.insert(token_id.clone(), amount);
+            transfers.push((token_id, amount));
+        }
+        self.internal_set_account(&account_id, account);
+        for (token_id, amount) in transfers {
+            self.transfer_reward(&token_id, &account_id, &receiver_id, amount);
+        }
+        result
+    }
+
+    /// Deposits attached NEAR as incentive of given proposal, 
+    /// NEAR must be whitelisted as an incentive token.
+    #[payable]
+    pub fn deposit_near_incentive(&mut self, proposal_id: u32, incentive_key: u32, vesting_duration_sec: Option<u32>) {
+        let amount = env::attached_deposit();
+        require!(amount > 0, E101_INSUFFICIENT_BALANCE);
+        self.deposit_reward(
+            &env::predecessor_account_id(),
+            proposal_id,
+            incentive_key,
+            &NEAR_REWARD_TOKEN_ID.to_string(),
+            amount,
+            vesting_duration_sec.unwrap_or(0),
+        );
     }
 
     /// Withdraws given reward token of given user.
     /// when amount is None, withdraw all balance of the token.
-    pub fn withdraw_reward(&mut self, token_id: AccountId, amount: Option<U128>) {
+    /// Vesting rewards can only be withdrawn as they vest.
+    /// With auto_register, an unregistered user would be registered on the NEP-141 reward token
+    /// by the reward storage budget, which needs more gas.
+    pub fn withdraw_reward(&mut self, token_id: RewardTokenId, amount: Option<U128>, auto_register: Option<bool>) {
         let account_id = env::predecessor_account_id();
         let mut account = self.internal_unwrap_account(&account_id);
+        account.release_vested_rewards();
 
         let total = account.rewards.get(&token_id).unwrap_or(&0_u128);
         let amount: u128 = amount.map(|v| v.into()).unwrap_or(*total);
@@ -45,15 +198,288 @@ impl Contract {
         if amount > 0 {
             // Note: subtraction, will be reverted if the promise fails.
             account.sub_reward(&token_id, amount);
+            let receiver_id = account.reward_receiver.clone().unwrap_or_else(|| account_id.clone());
             self.internal_set_account(&account_id, account);
-            self.transfer_reward(&token_id, &account_id, amount);
+            if auto_register.unwrap_or(false) && is_nep141_reward_token_id(&token_id) {
+                self.transfer_reward_with_registration(&token_id, &account_id, &receiver_id, amount);
+            } else {
+                self.transfer_reward(&token_id, &account_id, &receiver_id, amount);
+            }
+        }
+    }
+
+    /// Sets where withdrawn rewards go, None to withdraw to the account itself.
+    /// A failed transfer is always credited back to the account.
+    #[payable]
+    pub fn set_reward_receiver(&mut self, receiver_id: Option<AccountId>) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        account.reward_receiver = receiver_id.filter(|receiver_id| receiver_id != &account_id);
+        self.internal_set_account(&account_id, account);
+    }
+
+    /// Withdraws the incentives credited back to the caller when proposals were removed.
+    pub fn withdraw_incentive_refund(&mut self, token_id: RewardTokenId) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let mut refunds = self.data().incentive_refunds.get(&account_id).unwrap_or_default();
+        let amount = refunds.remove(&token_id).unwrap_or(0);
+        require!(amount > 0, E101_INSUFFICIENT_BALANCE);
+        if refunds.is_empty() {
+            self.data_mut().incentive_refunds.remove(&account_id);
+        } else {
+            self.data_mut().incentive_refunds.insert(&account_id, &refunds);
+        }
+
+        reward_token_transfer(&token_id, &account_id, amount, GAS_FOR_INCENTIVE_REFUND_WITHDRAW)
+        .then(ext_self::callback_withdraw_incentive_refund(
+            token_id,
+            account_id,
+            amount.into(),
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_INCENTIVE_REFUND_WITHDRAW,
+        ))
+    }
+
+    #[private]
+    pub fn callback_withdraw_incentive_refund(
+        &mut self,
+        token_id: RewardTokenId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let amount: Balance = amount.into();
+        let success = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                // This reverts the changes from withdraw function.
+                self.internal_add_incentive_refund(&receiver_id, &token_id, amount);
+                false
+            }
+        };
+        Event::IncentiveRefundWithdraw {
+            receiver_id: &receiver_id,
+            token_id: &token_id,
+            withdraw_amount: &U128(amount),
+            success,
+        }
+        .emit();
+    }
+
+    /// Anyone can refund an expired proposal's incentive which no voter could claim,
+    /// each depositor would get back what he deposited.
+    /// Depositors beyond the prepaid gas are left to the next call.
+    pub fn refund_unused_incentive(&mut self, proposal_id: u32, incentive_key: u32) {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        require!(proposal.status == Some(ProposalStatus::Expired), E409_PROPOSAL_NOT_EXPIRED);
+        let refunds = self.internal_take_unused_incentive(&mut proposal, incentive_key);
+        require!(!refunds.is_empty(), E210_NOTHING_TO_REFUND);
+        self.internal_set_proposal(proposal_id, proposal);
+        for (token_id, receiver_id, amount) in refunds {
+            self.transfer_unused_incentive((proposal_id, incentive_key), &token_id, &receiver_id, amount);
+        }
+    }
+
+    /// Anyone can sweep the rounding dust of an expired proposal to treasury
+    /// once all voters have claimed, the proposal would be settled.
+    /// The treasury is only needed when there is dust.
+    pub fn sweep_incentive_dust(&mut self, proposal_id: u32) {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        require!(proposal.status == Some(ProposalStatus::Expired), E409_PROPOSAL_NOT_EXPIRED);
+        let dust = self.internal_take_incentive_dust(&mut proposal);
+        self.internal_set_proposal(proposal_id, proposal);
+        if !dust.is_empty() {
+            let treasury_id = self.data().treasury_id.clone().expect(E309_TREASURY_NOT_SET);
+            for (incentive_key, token_id, amount) in dust {
+                self.transfer_incentive_dust((proposal_id, incentive_key), &token_id, &treasury_id, amount);
+            }
+        }
+    }
+
+    #[private]
+    pub fn callback_sweep_incentive_dust(
+        &mut self,
+        incentive_id: IncentiveId,
+        token_id: RewardTokenId,
+        treasury_id: AccountId,
+        amount: U128,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let amount: Balance = amount.into();
+        let (proposal_id, incentive_key) = incentive_id;
+        let success = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                // This reverts the changes from sweep function, so the dust can be swept again.
+                if let Some(mut proposal) = self.internal_get_proposal(proposal_id) {
+                    let incentive = proposal.incentive.get_mut(&incentive_key).unwrap();
+                    let index = incentive.incentive_token_ids.iter().position(|item| item == &token_id).unwrap();
+                    incentive.claimed_amounts[index] -= amount;
+                    proposal.is_settled = false;
+                    self.internal_set_proposal(proposal_id, proposal);
+                } else {
+                    // archived before the sweep failed, the dust goes to removed_proposal_assets
+                    self.internal_add_removed_proposal_assets(&token_id, amount);
+                }
+                false
+            }
+        };
+
+        Event::IncentiveDustSweep {
+            proposal_id,
+            incentive_key,
+            treasury_id: &treasury_id,
+            token_id: &token_id,
+            amount: &U128(amount),
+            success,
+        }
+        .emit();
+    }
+
+    #[private]
+    pub fn callback_refund_unused_incentive(
+        &mut self,
+        incentive_id: IncentiveId,
+        token_id: RewardTokenId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let amount: Balance = amount.into();
+        let (proposal_id, incentive_key) = incentive_id;
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => {
+                Event::IncentiveRefund {
+                    proposal_id,
+                    incentive_key,
+                    receiver_id: &receiver_id,
+                    token_id: &token_id,
+                    amount: &U128(amount),
+                    success: true,
+                }
+                .emit();
+            }
+            PromiseResult::Failed => {
+                // This reverts the changes from refund function, so the depositor can be refunded again.
+                if let Some(mut proposal) = self.internal_get_proposal(proposal_id) {
+                    let incentive = proposal.incentive.get_mut(&incentive_key).unwrap();
+                    let index = incentive.incentive_token_ids.iter().position(|item| item == &token_id).unwrap();
+                    incentive.claimed_amounts[index] -= amount;
+                    self.internal_set_proposal(proposal_id, proposal);
+                    self.internal_add_incentive_deposit(incentive_id, &token_id, &receiver_id, amount);
+                } else {
+                    // archived or removed before the refund failed, the depositor withdraws it by withdraw_incentive_refund
+                    self.internal_add_incentive_refund(&receiver_id, &token_id, amount);
+                }
+
+                Event::IncentiveRefund {
+                    proposal_id,
+                    incentive_key,
+                    receiver_id: &receiver_id,
+                    token_id: &token_id,
+                    amount: &U128(amount),
+                    success: false,
+                }
+                .emit();
+            }
+        }
+    }
+
+    #[private]
+    pub fn callback_reward_storage_balance_of(
+        &mut self,
+        token_id: RewardTokenId,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) {
+        require!(
+            env::promise_results_count() == 2,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let is_registered = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                // just try the transfer if the token answers something unexpected
+                near_sdk::serde_json::from_slice::<Option<StorageBalance>>(&value).map(|v| v.is_some()).unwrap_or(true)
+            },
+            PromiseResult::Failed => true,
+        };
+        let min_bound = match env::promise_result(1) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => {
+                near_sdk::serde_json::from_slice::<StorageBalanceBounds>(&value).map(|v| v.min.0).ok()
+            },
+            PromiseResult::Failed => None,
+        };
+        match min_bound {
+            Some(min_bound) if !is_registered && self.data().reward_storage_budget >= min_bound => {
+                // Note: subtraction, will be reverted if the deposit fails.
+                self.data_mut().reward_storage_budget -= min_bound;
+                Event::RewardStorageDeposit {
+                    receiver_id: &receiver_id,
+                    token_id: &token_id,
+                    amount: &U128(min_bound),
+                }
+                .emit();
+                ext_reward_token_storage::storage_deposit(
+                    Some(receiver_id.clone()),
+                    Some(true),
+                    token_id.parse().unwrap(),
+                    min_bound,
+                    GAS_FOR_REWARD_STORAGE_DEPOSIT,
+                )
+                .then(ext_self::callback_reward_storage_deposit(
+                    min_bound.into(),
+                    env::current_account_id(),
+                    0,
+                    GAS_FOR_RESOLVE_REWARD_STORAGE_DEPOSIT,
+                ))
+                .then(reward_token_transfer(&token_id, &receiver_id, amount.0, GAS_FOR_REWARD_TRANSFER))
+                .then(ext_self::callback_post_withdraw_reward(
+                    token_id,
+                    sender_id,
+                    amount,
+                    env::current_account_id(),
+                    0,
+                    GAS_FOR_RESOLVE_REWARD_TRANSFER,
+                ));
+            }
+            _ => self.transfer_reward(&token_id, &sender_id, &receiver_id, amount.0),
+        }
+    }
+
+    /// The reward transfer goes on after it whatever the result.
+    #[private]
+    pub fn callback_reward_storage_deposit(&mut self, deposit: U128) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        if let PromiseResult::Failed = env::promise_result(0) {
+            // This reverts the changes from callback_reward_storage_balance_of, a failed deposit is refunded.
+            self.data_mut().reward_storage_budget += deposit.0;
         }
     }
 
     #[private]
     pub fn callback_post_withdraw_reward(
         &mut self,
-        token_id: AccountId,
+        token_id: RewardTokenId,
         sender_id: AccountId,
         amount: U128,
     ) {
@@ -87,6 +513,7 @@ impl Contract {
                     }
                     .emit();
                 } else {
+                    self.internal_add_reward_lostfound(&sender_id, &token_id, amount);
                     Event::RewardLostfound {
                         caller_id: &sender_id,
                         token_id: &token_id,
@@ -101,65 +528,145 @@ impl Contract {
 
 impl Contract {
 
-    fn transfer_reward(&self, token_id: &AccountId, account_id: &AccountId, amount: Balance){
-        ext_fungible_token::ft_transfer(
+    /// Failed transfer is credited back to account_id, whoever the receiver is.
+    fn transfer_reward(&self, token_id: &RewardTokenId, account_id: &AccountId, receiver_id: &AccountId, amount: Balance){
+        reward_token_transfer(token_id, receiver_id, amount, GAS_FOR_REWARD_TRANSFER)
+        .then(ext_self::callback_post_withdraw_reward(
+            token_id.clone(),
             account_id.clone(),
             amount.into(),
-            None,
-            token_id.clone(),
-            1,
-            GAS_FOR_REWARD_TRANSFER,
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_REWARD_TRANSFER,
+        ));
+    }
+
+    /// Checks the storage of the receiver and the storage bounds on reward token first,
+    /// see callback_reward_storage_balance_of.
+    fn transfer_reward_with_registration(&self, token_id: &RewardTokenId, account_id: &AccountId, receiver_id: &AccountId, amount: Balance) {
+        ext_reward_token_storage::storage_balance_of(
+            receiver_id.clone(),
+            token_id.parse().unwrap(),
+            0,
+            GAS_FOR_STORAGE_BALANCE_OF,
         )
-        .then(ext_self::callback_post_withdraw_reward(
+        .and(ext_reward_token_storage::storage_balance_bounds(
+            token_id.parse().unwrap(),
+            0,
+            GAS_FOR_STORAGE_BALANCE_BOUNDS,
+        ))
+        .then(ext_self::callback_reward_storage_balance_of(
             token_id.clone(),
             account_id.clone(),
+            receiver_id.clone(),
             amount.into(),
             env::current_account_id(),
             0,
-            GAS_FOR_RESOLVE_REWARD_TRANSFER,
+            GAS_FOR_RESOLVE_REWARD_STORAGE,
+        ));
+    }
+
+    fn transfer_unused_incentive(&self, incentive_id: IncentiveId, token_id: &RewardTokenId, account_id: &AccountId, amount: Balance) {
+        reward_token_transfer(token_id, account_id, amount, GAS_FOR_INCENTIVE_REFUND)
+        .then(ext_self::callback_refund_unused_incentive(
+            incentive_id,
+            token_id.clone(),
+            account_id.clone(),
+            amount.into(),
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_INCENTIVE_REFUND,
         ));
     }
 
-    pub fn internal_claim_all(&mut self, account: &mut Account) {
+    fn transfer_incentive_dust(&self, incentive_id: IncentiveId, token_id: &RewardTokenId, treasury_id: &AccountId, amount: Balance) {
+        reward_token_transfer(token_id, treasury_id, amount, GAS_FOR_INCENTIVE_REFUND)
+        .then(ext_self::callback_sweep_incentive_dust(
+            incentive_id,
+            token_id.clone(),
+            treasury_id.clone(),
+            amount.into(),
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_INCENTIVE_REFUND,
+        ));
+    }
+
+    /// Claims one expired proposal the account voted, moving the vote into history.
+    /// The vote on a cancelled proposal is dropped with nothing claimed.
+    pub fn internal_claim_reward(&mut self, account: &mut Account, proposal_id: u32) -> Option<ClaimedReward> {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        if proposal.status == Some(ProposalStatus::Cancelled) {
+            return account.proposals.remove(&proposal_id).map(|_| {
+                proposal.claimed_count += 1;
+                self.internal_set_proposal(proposal_id, proposal);
+                (vec![], vec![])
+            });
+        }
+        if proposal.status == Some(ProposalStatus::Expired) {
+            if let Some(vote_detail) = account.proposals.remove(&proposal_id) {
+                let claimed = proposal.claim_reward(&vote_detail).unwrap_or_default();
+                self.internal_set_proposal(proposal_id, proposal.into());
+                let mut history = HashMap::new();
+                history.insert(proposal_id, vote_detail);
+                let mut rewards = HashMap::new();
+                for (token_id, amount) in claimed.0.iter() {
+                    rewards.insert(token_id.clone(), amount + rewards.get(token_id).unwrap_or(&0_u128));
+                }
+                account.add_history(&history, &rewards, &claimed.1, self.data().archive_history);
+                return Some(claimed);
+            }
+        }
+        None
+    }
+
+    /// Returns the rewards claimed, not including vesting rewards.
+    pub fn internal_claim_all(&mut self, account: &mut Account) -> HashMap<RewardTokenId, Balance> {
+        let archive = self.data().archive_history;
         let mut rewards = HashMap::new();
+        let mut vesting_rewards = vec![];
         let mut history = HashMap::new();
         account.proposals.retain(|proposal_id, vote_detail| {
             let mut proposal = self.internal_unwrap_proposal(*proposal_id);
             if proposal.status == Some(ProposalStatus::Expired) {
-                if let Some(reward_details) = proposal.claim_reward(vote_detail){
+                if let Some((reward_details, vesting_details)) = proposal.claim_reward(vote_detail){
                     reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
                         rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
                     });
+                    vesting_rewards.extend(vesting_details);
                 }
                 history.insert(*proposal_id, vote_detail.clone());
                 self.internal_set_proposal(*proposal_id, proposal.into());
                 false
+            } else if proposal.status == Some(ProposalStatus::Cancelled) {
+                proposal.claimed_count += 1;
+                self.internal_set_proposal(*proposal_id, proposal);
+                false
             } else {
                 true
             }
         });
+        account.add_history(&history, &rewards, &vesting_rewards, archive);
         account.add_rewards(&rewards);
-        account.add_history(&history);
+        account.add_vesting_rewards(vesting_rewards);
+        rewards
     }
 
 
-    pub fn internal_calc_account_unclaim_rewards(&self, account_id: &AccountId) -> HashMap<AccountId, Balance> {
+    pub fn internal_calc_account_unclaim_rewards(&self, account_id: &AccountId) -> HashMap<RewardTokenId, Balance> {
         let account = self.internal_unwrap_account(account_id);
         let mut rewards = HashMap::new();
         for (proposal_id, vote_detail) in account.proposals {
             let proposal = self.internal_unwrap_proposal(proposal_id);
             if proposal.status == Some(ProposalStatus::Expired) {
-                let incentive_key = if let ProposalKind::FarmingReward { .. } = proposal.kind {
-                    vote_detail.action.get_index() as u32
-                } else {
-                    0
-                };
-                if let Some(incentive) = proposal.incentive.get(&incentive_key) {
-                    let votes_total_amount = proposal.get_votes_total_amount_for_reward_calc(incentive_key);
-                    let reward_details = incentive.calc_reward(vote_detail.amount, votes_total_amount);
-                    reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
-                        rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
-                    });
+                for incentive_key in proposal.get_incentive_keys_for_reward_calc(&vote_detail.action) {
+                    if let Some(incentive) = proposal.incentive.get(&incentive_key) {
+                        let votes_total_amount = proposal.get_votes_total_amount_for_reward_calc(incentive_key);
+                        let reward_details = incentive.calc_reward(vote_detail.amount, votes_total_amount);
+                        reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
+                            rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
+                        });
+                    }
                 }
             }
         }
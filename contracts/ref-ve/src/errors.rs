@@ -11,6 +11,8 @@ pub const E103_STILL_HAS_REWARD: &str = "E103: still has reward";
 pub const E104_STILL_HAS_LPT: &str = "E104: still has lpt";
 pub const E105_ACC_NOT_LOCKED: &str = "E105: account not locked";
 pub const E106_STILL_HAS_LOVE_TOKEN: &str = "E106: still has love token";
+pub const E107_TOO_MANY_ACCOUNTS: &str = "E107: too many accounts";
+pub const E108_STILL_HAS_VOTE_HISTORY: &str = "E108: still has vote history";
 
 pub const E200_ALREADY_VOTED: &str = "E200: already voted";
 pub const E201_INVALID_VOTE: &str = "E201: invalid vote";
@@ -21,6 +23,14 @@ pub const E205_NOT_VOTABLE: &str = "E205: not votable";
 pub const E206_NO_VOTED: &str = "E206: no voted";
 pub const E207_INVALID_INCENTIVE_KEY: &str = "E207: invalid incentive key";
 pub const E208_DESCRIPTION_TOO_LONG: &str = "E208: description len need less than or equal to 2048";
+pub const E209_INCENTIVE_STILL_CLAIMABLE: &str = "E209: incentive still claimable";
+pub const E210_NOTHING_TO_REFUND: &str = "E210: nothing to refund";
+pub const E211_PROPOSAL_SETTLED: &str = "E211: proposal already settled";
+pub const E212_REWARD_NOT_ALL_CLAIMED: &str = "E212: reward not all claimed";
+pub const E213_UNUSED_INCENTIVE_NOT_REFUNDED: &str = "E213: unused incentive not refunded";
+pub const E214_INVALID_VESTING_DURATION: &str = "E214: invalid vesting duration";
+pub const E215_METADATA_TOO_LONG: &str = "E215: metadata too long";
+pub const E216_INVALID_CONTENT_HASH: &str = "E216: invalid content hash";
 
 pub const E301_INVALID_RATIO: &str = "E301: invalid ratio";
 pub const E302_INVALID_DURATION: &str = "E302: invalid duration";
@@ -30,10 +40,23 @@ pub const E305_STILL_IN_LOCK: &str = "E305: still in locking";
 pub const E306_INVALID_LOCK_DURATION_LIMIT: &str = "E306: invalid lock duration limit";
 pub const E307_INVALID_VOTING_DURATION_LIMIT: &str = "E307: invalid voting duration limit";
 pub const E308_UNECONOMIC_LOCK: &str = "E308: uneconomic lock";
+pub const E309_TREASURY_NOT_SET: &str = "E309: treasury not set";
+pub const E310_INSUFFICIENT_STORAGE_BUDGET: &str = "E310: insufficient reward storage budget";
 
 pub const E402_INVALID_START_TIME: &str = "E402: invalid start time";
 pub const E404_PROPOSAL_NOT_EXIST: &str = "E404: proposal not exist";
 pub const E406_EXPIRED_PROPOSAL: &str = "E406: expired proposal";
+pub const E407_INVALID_SCHEDULE: &str = "E407: invalid proposal schedule";
+pub const E408_SCHEDULE_NOT_EXIST: &str = "E408: proposal schedule not exist";
+pub const E409_PROPOSAL_NOT_EXPIRED: &str = "E409: proposal not expired";
+pub const E410_PROPOSAL_NOT_SETTLED: &str = "E410: proposal not settled";
+pub const E411_PROPOSAL_ARCHIVED: &str = "E411: proposal archived";
+pub const E412_PROPOSAL_NOT_WARMUP: &str = "E412: proposal not in warm up";
+pub const E413_INVALID_OPTIONS: &str = "E413: invalid options";
+pub const E414_PROPOSAL_CANCELLED: &str = "E414: proposal cancelled";
+pub const E415_PROPOSAL_CLAIMED: &str = "E415: proposal has been claimed";
+pub const E416_INVALID_TAGS: &str = "E416: invalid tags";
+pub const E417_SCHEDULE_OVERFLOW: &str = "E417: proposal schedule time overflow";
 
 pub const E500_INVALID_MSG: &str = "E500: invalid msg";
 pub const E501_INVALID_FARM_INFO: &str = "E501: invalid farm info";
@@ -20,9 +20,57 @@ pub enum Event<'a> {
         proposer_id: &'a AccountId,
         proposal_id: u32,
     },
+    ProposalCancel {
+        guardian_id: &'a AccountId,
+        proposal_id: u32,
+        reason: &'a str,
+    },
+    ProposalUpdate {
+        proposer_id: &'a AccountId,
+        proposal_id: u32,
+        revision: u32,
+        start_at: u64,
+        duration_sec: u32,
+    },
+    ProposalArchive {
+        proposal_id: u32,
+        content_hash: &'a Base58CryptoHash,
+    },
+    ProposalScheduleRound {
+        schedule_id: u32,
+        round: u32,
+        proposal_id: u32,
+    },
+    ProposalScheduleSkip {
+        schedule_id: u32,
+        reason: &'a str,
+    },
     RemovedProposalAssets {
         receiver_id: &'a AccountId,
-        token_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
+        amount: &'a U128,
+        success: bool,
+    },
+    IncentiveRefund {
+        proposal_id: u32,
+        incentive_key: u32,
+        receiver_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
+        amount: &'a U128,
+
+        success: bool,
+    },
+    IncentiveRefundWithdraw {
+        receiver_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
+        withdraw_amount: &'a U128,
+        success: bool,
+    },
+    IncentiveDustSweep {
+        proposal_id: u32,
+        incentive_key: u32,
+        treasury_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
         amount: &'a U128,
         success: bool,
     },
@@ -46,23 +94,39 @@ pub enum Event<'a> {
         withdraw_amount: &'a U128,
         success: bool,
     },
+    ProposalHistoryPrune {
+        caller_id: &'a AccountId,
+        before_proposal_id: u32,
+        pruned_count: u32,
+    },
     RewardWithdraw {
         caller_id: &'a AccountId,
-        token_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
         withdraw_amount: &'a U128,
         success: bool,
     },
     RewardLostfound {
         caller_id: &'a AccountId,
-        token_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
         withdraw_amount: &'a U128,
     },
+    RewardWithdrawLostfound {
+        receiver_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
+        withdraw_amount: &'a U128,
+        success: bool,
+    },
+    RewardStorageDeposit {
+        receiver_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
+        amount: &'a U128,
+    },
     
     RewardDeposit {
         caller_id: &'a AccountId,
         proposal_id: u32,
         incentive_key: u32,
-        token_id: &'a AccountId,
+        token_id: &'a RewardTokenId,
         deposit_amount: &'a U128,
         total_amount: &'a U128,
         start_at: u64,
@@ -115,8 +179,8 @@ mod tests {
         AccountId::new_unchecked("alice".to_string())
     }
 
-    fn token_id() -> AccountId {
-        AccountId::new_unchecked("ref".to_string())
+    fn token_id() -> RewardTokenId {
+        "ref".to_string()
     }
 
     #[test]
@@ -133,6 +197,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_proposal_schedule_round() {
+        let schedule_id = 0;
+        let round = 1;
+        let proposal_id = 2;
+        Event::ProposalScheduleRound { schedule_id, round, proposal_id }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_schedule_round","data":[{"schedule_id":0,"round":1,"proposal_id":2}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_schedule_skip() {
+        let schedule_id = 0;
+        let reason = E302_INVALID_DURATION;
+        Event::ProposalScheduleSkip { schedule_id, reason }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_schedule_skip","data":[{"schedule_id":0,"reason":"E302: invalid duration"}]}"#
+        );
+    }
+
     #[test]
     fn event_proposal_remove() {
         let proposer_id = &alice();
@@ -144,6 +231,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_proposal_cancel() {
+        let guardian_id = &alice();
+        let proposal_id = 0;
+        let reason = "malicious";
+        Event::ProposalCancel { guardian_id, proposal_id, reason }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_cancel","data":[{"guardian_id":"alice","proposal_id":0,"reason":"malicious"}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_update() {
+        let proposer_id = &alice();
+        let proposal_id = 0;
+        let revision = 1;
+        let start_at = 1000_u64;
+        let duration_sec = 500_u32;
+        Event::ProposalUpdate { proposer_id, proposal_id, revision, start_at, duration_sec }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_update","data":[{"proposer_id":"alice","proposal_id":0,"revision":1,"start_at":1000,"duration_sec":500}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_archive() {
+        let proposal_id = 0;
+        let content_hash = &Base58CryptoHash::from([0u8; 32]);
+        Event::ProposalArchive { proposal_id, content_hash }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_archive","data":[{"proposal_id":0,"content_hash":"11111111111111111111111111111111"}]}"#
+        );
+    }
+
     #[test]
     fn event_removed_proposal_assets() {
         let receiver_id = &alice();
@@ -157,6 +281,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_incentive_refund_withdraw() {
+        let receiver_id = &alice();
+        let token_id = &token_id();
+        let withdraw_amount = &U128(100);
+        let success = true;
+        Event::IncentiveRefundWithdraw { receiver_id, token_id, withdraw_amount, success }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"incentive_refund_withdraw","data":[{"receiver_id":"alice","token_id":"ref","withdraw_amount":"100","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_incentive_refund() {
+        let proposal_id = 0;
+        let incentive_key = 1;
+        let receiver_id = &alice();
+        let token_id = &token_id();
+        let amount = &U128(100);
+        let success = true;
+        Event::IncentiveRefund { proposal_id, incentive_key, receiver_id, token_id, amount, success }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"incentive_refund","data":[{"proposal_id":0,"incentive_key":1,"receiver_id":"alice","token_id":"ref","amount":"100","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_incentive_dust_sweep() {
+        let proposal_id = 0;
+        let incentive_key = 1;
+        let treasury_id = &alice();
+        let token_id = &token_id();
+        let amount = &U128(100);
+        let success = true;
+        Event::IncentiveDustSweep { proposal_id, incentive_key, treasury_id, token_id, amount, success }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"incentive_dust_sweep","data":[{"proposal_id":0,"incentive_key":1,"treasury_id":"alice","token_id":"ref","amount":"100","success":true}]}"#
+        );
+    }
+
     #[test]
     fn event_action_proposal() {
         let voter_id = &alice();
@@ -205,6 +372,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_proposal_history_prune() {
+        let caller_id = &alice();
+        let before_proposal_id = 10;
+        let pruned_count = 3;
+        Event::ProposalHistoryPrune { caller_id, before_proposal_id, pruned_count }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_history_prune","data":[{"caller_id":"alice","before_proposal_id":10,"pruned_count":3}]}"#
+        );
+    }
+
     #[test]
     fn event_reward_withdraw() {
         let caller_id = &alice();
@@ -230,6 +409,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_reward_withdraw_lostfound() {
+        let receiver_id = &alice();
+        let token_id = &token_id();
+        let withdraw_amount = &U128(100);
+        let success = true;
+        Event::RewardWithdrawLostfound { receiver_id, token_id, withdraw_amount, success }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"reward_withdraw_lostfound","data":[{"receiver_id":"alice","token_id":"ref","withdraw_amount":"100","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_reward_storage_deposit() {
+        let receiver_id = &alice();
+        let token_id = &token_id();
+        let amount = &U128(100);
+        Event::RewardStorageDeposit { receiver_id, token_id, amount }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"reward_storage_deposit","data":[{"receiver_id":"alice","token_id":"ref","amount":"100"}]}"#
+        );
+    }
+
     #[test]
     fn event_reward_deposit() {
         let caller_id = &alice();
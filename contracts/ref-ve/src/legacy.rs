@@ -1,5 +1,7 @@
 use crate::*;
 
+// Token ids were AccountId before RewardTokenId, both are stored as a string.
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct ConfigV0100 {
     pub min_proposal_start_vote_offset_sec: u32,
@@ -26,7 +28,7 @@ pub struct ContractDataV0100 {
     pub owner_id: AccountId,
     pub operators: UnorderedSet<AccountId>,
     pub whitelisted_accounts: UnorderedSet<AccountId>,
-    pub whitelisted_incentive_tokens: HashSet<AccountId>,
+    pub whitelisted_incentive_tokens: HashSet<RewardTokenId>,
     pub config: LazyOption<ConfigV0100>,
     pub symbol: String,
     pub lptoken_contract_id: AccountId,
@@ -39,10 +41,10 @@ pub struct ContractDataV0100 {
     pub cur_total_ve_lpt: Balance,
     pub cur_lock_lpt: Balance,
     pub lostfound: Balance,
-    pub removed_proposal_assets: UnorderedMap<AccountId, Balance>
+    pub removed_proposal_assets: UnorderedMap<RewardTokenId, Balance>
 }
 
-impl From<ContractDataV0100> for ContractData {
+impl From<ContractDataV0100> for ContractDataV0200 {
     fn from(a: ContractDataV0100) -> Self {
         let ContractDataV0100 {
             owner_id,
@@ -83,4 +85,210 @@ impl From<ContractDataV0100> for ContractData {
             removed_proposal_assets
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractDataV0200 {
+    pub owner_id: AccountId,
+    pub operators: UnorderedSet<AccountId>,
+    pub whitelisted_accounts: UnorderedSet<AccountId>,
+    pub whitelisted_incentive_tokens: HashSet<RewardTokenId>,
+    pub config: LazyOption<Config>,
+    pub symbol: String,
+    pub lptoken_contract_id: AccountId,
+    pub lptoken_id: String,
+    pub lptoken_decimals: u8,
+    pub last_proposal_id: u32,
+    pub proposals: UnorderedMap<u32, VProposal>,
+    pub accounts: LookupMap<AccountId, VAccount>,
+    pub account_count: u64,
+    pub cur_total_ve_lpt: Balance,
+    pub cur_lock_lpt: Balance,
+    pub lostfound: Balance,
+    pub removed_proposal_assets: UnorderedMap<RewardTokenId, Balance>
+}
+
+impl From<ContractDataV0200> for ContractData {
+    fn from(a: ContractDataV0200) -> Self {
+        let ContractDataV0200 {
+            owner_id,
+            operators,
+            whitelisted_accounts,
+            whitelisted_incentive_tokens,
+            config,
+            symbol,
+            lptoken_contract_id,
+            lptoken_id,
+            lptoken_decimals,
+            last_proposal_id,
+            proposals,
+            accounts,
+            account_count,
+            cur_total_ve_lpt,
+            cur_lock_lpt,
+            lostfound,
+            removed_proposal_assets
+        } = a;
+        Self {
+            owner_id,
+            operators,
+            whitelisted_accounts,
+            whitelisted_incentive_tokens,
+            config,
+            symbol,
+            lptoken_contract_id,
+            lptoken_id,
+            lptoken_decimals,
+            last_proposal_id,
+            proposals,
+            accounts,
+            account_count,
+            account_index: UnorderedSet::new(StorageKeys::AccountIndex),
+            cur_total_ve_lpt,
+            cur_lock_lpt,
+            lostfound,
+            removed_proposal_assets,
+            treasury_id: None,
+            reward_storage_budget: 0,
+            keeper_fee_bps: 0,
+            archive_history: false,
+            last_proposal_schedule_id: 0,
+            proposal_schedules: UnorderedMap::new(StorageKeys::ProposalSchedules),
+            incentive_deposits: LookupMap::new(StorageKeys::IncentiveDeposits),
+            reward_lostfound: UnorderedMap::new(StorageKeys::RewardLostfound),
+            lpt_lostfound: UnorderedMap::new(StorageKeys::LptLostfound),
+            incentive_refunds: LookupMap::new(StorageKeys::IncentiveRefunds),
+            guardians: UnorderedSet::new(StorageKeys::Guardian),
+            proposal_index: LookupMap::new(StorageKeys::ProposalIndex),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ProposalV0200 {
+    pub id: u32,
+    pub description: String,
+    pub proposer: AccountId,
+    pub kind: ProposalKind,
+    pub votes: Vec<VoteInfo>,
+    pub ve_amount_at_last_action: u128,
+    pub start_at: Timestamp,
+    pub end_at: Timestamp,
+    pub participants: u64,
+    pub incentive: HashMap<u32, ProposalIncentiveV0200>,
+}
+
+impl From<ProposalV0200> for Proposal {
+    fn from(a: ProposalV0200) -> Self {
+        let ProposalV0200 {
+            id,
+            description,
+            proposer,
+            kind,
+            votes,
+            ve_amount_at_last_action,
+            start_at,
+            end_at,
+            participants,
+            incentive
+        } = a;
+        Self {
+            id,
+            description,
+            metadata: None,
+            proposer,
+            kind,
+            votes,
+            ve_amount_at_last_action,
+            start_at,
+            end_at,
+            participants,
+            incentive: incentive.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            claimed_count: 0,
+            // a proposal still stored in this layout has not been written since upgrade,
+            // so only an expired one could have been claimed, by voters we can't count.
+            has_untracked_claims: env::block_timestamp() >= end_at,
+            is_settled: false,
+            storage_deposit: 0,
+            revision: 0,
+            cancellation: None,
+            tags: vec![],
+            status: None,
+            is_nonsense: None,
+        }
+    }
+}
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ProposalIncentiveV0200 {
+    pub incentive_token_ids: Vec<RewardTokenId>,
+    pub incentive_amounts: Vec<Balance>,
+    pub claimed_amounts: Vec<Balance>,
+}
+
+impl From<ProposalIncentiveV0200> for ProposalIncentive {
+    fn from(a: ProposalIncentiveV0200) -> Self {
+        let ProposalIncentiveV0200 {
+            incentive_token_ids,
+            incentive_amounts,
+            claimed_amounts,
+        } = a;
+        Self {
+            incentive_token_ids,
+            incentive_amounts,
+            claimed_amounts,
+            vesting_duration_sec: 0,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct AccountV0200 {
+    pub sponsor_id: AccountId,
+    pub lpt_amount: Balance,
+    pub ve_lpt_amount: Balance,
+    pub unlock_timestamp: u64,
+    pub duration_sec: u32,
+    pub proposals: HashMap<u32, VoteDetail>,
+    pub proposals_history: UnorderedMap<u32, VoteDetail>,
+    pub rewards: HashMap<RewardTokenId, Balance>,
+}
+
+impl From<AccountV0200> for Account {
+    fn from(a: AccountV0200) -> Self {
+        let AccountV0200 {
+            sponsor_id,
+            lpt_amount,
+            ve_lpt_amount,
+            unlock_timestamp,
+            duration_sec,
+            proposals,
+            proposals_history,
+            rewards,
+        } = a;
+        // registered by storage_deposit with the fixed storage fee
+        let storage_balance = if sponsor_id == env::current_account_id() { 0 } else { STORAGE_BALANCE_MIN_BOUND };
+        // rewards earned before 0.3.0 are not recorded
+        let history_stats = HistoryStats {
+            votes_cast: proposals_history.len() as u32,
+            rewards_earned: HashMap::new(),
+        };
+        Self {
+            sponsor_id,
+            lpt_amount,
+            ve_lpt_amount,
+            unlock_timestamp,
+            duration_sec,
+            proposals,
+            proposals_history,
+            history_stats,
+            rewards,
+            vesting_rewards: vec![],
+            reward_receiver: None,
+            keeper_claim_disabled: false,
+            storage_balance,
+            storage_usage: 0,
+            // the size re-encoded as Current is the baseline
+            is_baseline_pending: true,
+        }
+    }
+}
@@ -12,12 +12,16 @@ mod actions_of_reward;
 mod proposals;
 mod proposals_action;
 mod proposals_incentive;
+mod proposals_schedule;
+mod proposals_index;
+mod proposals_result;
 mod token_receiver;
 mod storage_impl;
 mod errors;
 mod events;
 mod utils;
 mod views;
+mod previews;
 mod legacy;
 
 pub use crate::owner::*;
@@ -29,12 +33,16 @@ pub use crate::actions_of_reward::*;
 pub use crate::proposals::*;
 pub use crate::proposals_action::*;
 pub use crate::proposals_incentive::*;
+pub use crate::proposals_schedule::*;
+pub use crate::proposals_index::*;
+pub use crate::proposals_result::*;
 pub use crate::token_receiver::*;
 pub use crate::storage_impl::*;
 pub use crate::errors::*;
 pub use crate::events::*;
 pub use crate::utils::*;
 pub use crate::views::*;
+pub use crate::previews::*;
 pub use crate::legacy::*;
 
 use near_contract_standards::fungible_token::metadata::{
@@ -43,13 +51,13 @@ use near_contract_standards::fungible_token::metadata::{
 use near_contract_standards::fungible_token::FungibleToken;
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::U128;
+use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{Base58CryptoHash, U128};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::BorshStorageKey;
 use near_sdk::{
     assert_one_yocto, env, near_bindgen, require, AccountId, Balance, PanicOnDefault, Promise, PromiseOrValue,
-    PromiseResult, Timestamp, log
+    PromiseResult, StorageUsage, Timestamp, log
 };
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -62,7 +70,17 @@ pub(crate) enum StorageKeys {
     WhitelistedAccounts,
     Proposals,
     AccountProposalHistory { account_id: AccountId },
-    RemovedProposalAssets
+    RemovedProposalAssets,
+    ProposalSchedules,
+    IncentiveDeposits,
+    IncentiveDepositsItem { proposal_id: u32, incentive_key: u32 },
+    RewardLostfound,
+    LptLostfound,
+    IncentiveRefunds,
+    Guardian,
+    ProposalIndex,
+    ProposalIndexItem { key: Vec<u8> },
+    AccountIndex,
 }
 
 /// Contract config
@@ -119,7 +137,7 @@ pub struct ContractData {
     pub owner_id: AccountId,
     pub operators: UnorderedSet<AccountId>,
     pub whitelisted_accounts: UnorderedSet<AccountId>,
-    pub whitelisted_incentive_tokens: HashSet<AccountId>,
+    pub whitelisted_incentive_tokens: HashSet<RewardTokenId>,
 
     pub config: LazyOption<Config>,
 
@@ -138,6 +156,8 @@ pub struct ContractData {
 
     pub accounts: LookupMap<AccountId, VAccount>,
     pub account_count: u64,
+    /// Ids of the accounts for listing, accounts registered before 0.3.0 are added by index_accounts.
+    pub account_index: UnorderedSet<AccountId>,
 
     // total ve lpt amount
     pub cur_total_ve_lpt: Balance,
@@ -147,14 +167,44 @@ pub struct ContractData {
     // if withdraw lpt encounter error, the lpt would go to here
     pub lostfound: Balance,
 
-    pub removed_proposal_assets: UnorderedMap<AccountId, Balance>
+    pub removed_proposal_assets: UnorderedMap<RewardTokenId, Balance>,
+
+    /// Where the incentive dust of settled proposals goes.
+    pub treasury_id: Option<AccountId>,
+    /// NEAR to register voters on reward tokens when withdrawing
+    pub reward_storage_budget: Balance,
+    /// Fee in bps of claimed rewards paid to keepers claiming on behalf of accounts
+    pub keeper_fee_bps: u32,
+    /// Only history_stats of accounts are kept when claiming, not the votes.
+    pub archive_history: bool,
+
+    /// Last available id for the proposal schedules.
+    pub last_proposal_schedule_id: u32,
+    /// Recurring proposal templates materialized by keepers.
+    pub proposal_schedules: UnorderedMap<u32, ProposalSchedule>,
+
+    /// Deposited amount of each (token_id, depositor) of incentives.
+    pub incentive_deposits: LookupMap<IncentiveId, UnorderedMap<(RewardTokenId, AccountId), Balance>>,
+
+    // if withdraw reward encounter error after the account is removed, the reward would go to here
+    pub reward_lostfound: UnorderedMap<(AccountId, RewardTokenId), Balance>,
+    /// Who the lostfound lpt belongs to, lpt lost before 0.3.0 are not recorded here.
+    pub lpt_lostfound: UnorderedMap<AccountId, Balance>,
+    /// Incentives of removed proposals credited back to their depositors.
+    pub incentive_refunds: LookupMap<AccountId, HashMap<RewardTokenId, Balance>>,
+    /// Who can cancel proposals in any state, managed by owner.
+    pub guardians: UnorderedSet<AccountId>,
+    /// Proposal ids by proposer, kind and tag.
+    pub proposal_index: LookupMap<ProposalIndexKey, TreeMap<u32, ()>>,
 }
 
 /// Versioned contract data. Allows to easily upgrade contracts.
 #[derive(BorshSerialize, BorshDeserialize)]
+#[allow(clippy::large_enum_variant)]
 pub enum VersionedContractData {
     V0100(ContractDataV0100),
-    V0200(ContractData),
+    V0200(ContractDataV0200),
+    V0300(ContractData),
 }
 
 #[near_bindgen]
@@ -171,7 +221,7 @@ impl Contract {
         require!(!env::state_exists(), E000_ALREADY_INIT);
         Self {
             ft: FungibleToken::new(b"a".to_vec()),
-            data: VersionedContractData::V0200(ContractData {
+            data: VersionedContractData::V0300(ContractData {
                 owner_id,
                 operators: UnorderedSet::new(StorageKeys::Operator),
                 whitelisted_accounts: UnorderedSet::new(StorageKeys::WhitelistedAccounts),
@@ -185,10 +235,23 @@ impl Contract {
                 proposals: UnorderedMap::new(StorageKeys::Proposals),
                 accounts: LookupMap::new(StorageKeys::Accounts),
                 account_count: 0,
+                account_index: UnorderedSet::new(StorageKeys::AccountIndex),
                 cur_total_ve_lpt: 0,
                 cur_lock_lpt: 0,
                 lostfound: 0,
                 removed_proposal_assets: UnorderedMap::new(StorageKeys::RemovedProposalAssets),
+                treasury_id: None,
+                reward_storage_budget: 0,
+                keeper_fee_bps: 0,
+                archive_history: false,
+                last_proposal_schedule_id: 0,
+                proposal_schedules: UnorderedMap::new(StorageKeys::ProposalSchedules),
+                incentive_deposits: LookupMap::new(StorageKeys::IncentiveDeposits),
+                reward_lostfound: UnorderedMap::new(StorageKeys::RewardLostfound),
+                lpt_lostfound: UnorderedMap::new(StorageKeys::LptLostfound),
+                incentive_refunds: LookupMap::new(StorageKeys::IncentiveRefunds),
+                guardians: UnorderedSet::new(StorageKeys::Guardian),
+                proposal_index: LookupMap::new(StorageKeys::ProposalIndex),
             }),
         }
     }
@@ -202,7 +265,7 @@ impl Contract {
     #[allow(unreachable_patterns)]
     fn data(&self) -> &ContractData {
         match &self.data {
-            VersionedContractData::V0200(data) => data,
+            VersionedContractData::V0300(data) => data,
             _ => unimplemented!(),
         }
     }
@@ -210,7 +273,7 @@ impl Contract {
     #[allow(unreachable_patterns)]
     fn data_mut(&mut self) -> &mut ContractData {
         match &mut self.data {
-            VersionedContractData::V0200(data) => data,
+            VersionedContractData::V0300(data) => data,
             _ => unimplemented!(),
         }
     }
@@ -1,5 +1,4 @@
 use crate::*;
-use near_contract_standards::fungible_token::core_impl::ext_fungible_token;
 
 #[near_bindgen]
 impl Contract {
@@ -22,17 +21,47 @@ impl Contract {
         }
     }
 
+    /// Adds accounts registered before the account index existed to it,
+    /// at most ACCOUNT_INDEX_BATCH_LIMIT a call. Unregistered or already indexed ones are skipped.
+    /// Returns the number of accounts newly indexed.
     #[payable]
-    pub fn extend_whitelisted_incentive_tokens(&mut self, tokens: Vec<AccountId>) {
+    pub fn index_accounts(&mut self, account_ids: Vec<AccountId>) -> u32 {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        require!(account_ids.len() <= ACCOUNT_INDEX_BATCH_LIMIT, E107_TOO_MANY_ACCOUNTS);
+        let mut count = 0;
+        for account_id in account_ids {
+            if self.data().accounts.contains_key(&account_id) && self.data_mut().account_index.insert(&account_id) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Adds proposals created before the proposal indexes existed to them,
+    /// at most PROPOSAL_INDEX_BATCH_LIMIT ids from from_proposal_id a call.
+    /// Returns the number of proposals newly indexed.
+    #[payable]
+    pub fn index_proposals(&mut self, from_proposal_id: u32, limit: Option<u32>) -> u32 {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        let limit = std::cmp::min(limit.unwrap_or(PROPOSAL_INDEX_BATCH_LIMIT), PROPOSAL_INDEX_BATCH_LIMIT);
+        self.internal_index_proposals(from_proposal_id, limit)
+    }
+
+    /// Token ids are the account id of a NEP-141 token, "{contract_id}@{token_id}" of a MFT, or "NEAR".
+    #[payable]
+    pub fn extend_whitelisted_incentive_tokens(&mut self, tokens: Vec<RewardTokenId>) {
         assert_one_yocto();
         require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
         for token in tokens {
+            require!(is_valid_reward_token_id(&token), E502_INVALID_TOKEN_ID);
             self.data_mut().whitelisted_incentive_tokens.insert(token);
         }
     }
 
     #[payable]
-    pub fn remove_whitelisted_incentive_tokens(&mut self, tokens: Vec<AccountId>) {
+    pub fn remove_whitelisted_incentive_tokens(&mut self, tokens: Vec<RewardTokenId>) {
         assert_one_yocto();
         require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
         for token in tokens {
@@ -40,6 +69,22 @@ impl Contract {
         }
     }
 
+    #[payable]
+    pub fn modify_keeper_fee_bps(&mut self, keeper_fee_bps: u32) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        require!(keeper_fee_bps <= MAX_KEEPER_FEE_BPS, E301_INVALID_RATIO);
+        self.data_mut().keeper_fee_bps = keeper_fee_bps;
+    }
+
+    /// In archival mode, claiming only counts votes and rewards into history_stats of accounts.
+    #[payable]
+    pub fn modify_archive_history(&mut self, archive_history: bool) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        self.data_mut().archive_history = archive_history;
+    }
+
     #[payable]
     pub fn modify_min_start_vote_offset_sec(&mut self, min_start_vote_offset_sec: u32) {
         assert_one_yocto();
@@ -78,23 +123,99 @@ impl Contract {
         self.data_mut().config.set(&config);
     }
 
+    /// Register a recurring proposal, the caller would be the proposer of every round.
+    /// Rounds are created by materialize_scheduled_proposals 
+    /// once now is within lead_sec before their voting begin time.
+    /// The attached deposit pays for the storage of the schedule, the rest prepays the rounds.
+    #[payable]
+    pub fn register_proposal_schedule(
+        &mut self,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        interval_sec: u32,
+        lead_sec: u32,
+    ) -> u32 {
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+
+        let proposer = env::predecessor_account_id();
+        require!(self.data().whitelisted_accounts.contains(&proposer), E002_NOT_ALLOWED);
+        require!(description.len() <= DESCRIPTION_LIMIT, E208_DESCRIPTION_TOO_LONG);
+        self.internal_unwrap_account(&proposer);
+
+        let config = self.internal_config();
+        require!(duration_sec >= config.min_voting_duration_sec && duration_sec <= config.max_voting_duration_sec
+            , E302_INVALID_DURATION);
+        require!(start_at >= nano_to_sec(env::block_timestamp()) + config.min_proposal_start_vote_offset_sec, E402_INVALID_START_TIME);
+        require!(interval_sec > 0 && interval_sec >= duration_sec && lead_sec >= config.min_proposal_start_vote_offset_sec, E407_INVALID_SCHEDULE);
+        require!(start_at.checked_add(interval_sec).is_some(), E417_SCHEDULE_OVERFLOW);
+
+        let id = self.data().last_proposal_schedule_id;
+        let prev_storage = env::storage_usage();
+        let mut schedule = ProposalSchedule {
+            id,
+            proposer,
+            kind,
+            description,
+            next_start_at: start_at,
+            duration_sec,
+            interval_sec,
+            lead_sec,
+            rounds: 0,
+            last_proposal_id: None,
+            storage_balance: 0,
+        };
+        self.internal_set_proposal_schedule(id, &schedule);
+        let storage_cost = (env::storage_usage() - prev_storage) as Balance * env::storage_byte_cost();
+        let attached_deposit = env::attached_deposit();
+        require!(attached_deposit >= std::cmp::max(storage_cost, 1), E102_INSUFFICIENT_STORAGE);
+        schedule.storage_balance = attached_deposit - storage_cost;
+        self.internal_set_proposal_schedule(id, &schedule);
+        self.data_mut().last_proposal_schedule_id += 1;
+        id
+    }
+
+    /// Adds the attached deposit to the storage balance of a proposal schedule.
+    #[payable]
+    pub fn top_up_proposal_schedule(&mut self, schedule_id: u32) {
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        let mut schedule = self.internal_get_proposal_schedule(schedule_id).expect(E408_SCHEDULE_NOT_EXIST);
+        schedule.storage_balance += env::attached_deposit();
+        self.internal_set_proposal_schedule(schedule_id, &schedule);
+    }
+
+    /// Remove a proposal schedule, rounds already materialized are not affected.
+    /// The storage balance and the storage released go back to the proposer of the schedule.
+    #[payable]
+    pub fn remove_proposal_schedule(&mut self, schedule_id: u32) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        let prev_storage = env::storage_usage();
+        let schedule = self.data_mut().proposal_schedules.remove(&schedule_id).expect(E408_SCHEDULE_NOT_EXIST);
+        let released = prev_storage.saturating_sub(env::storage_usage()) as Balance * env::storage_byte_cost();
+        let refund = schedule.storage_balance + released;
+        if refund > 0 {
+            Promise::new(schedule.proposer).transfer(refund);
+        }
+    }
+
     /// owner help to return those who lost lpt when withdraw,
     /// It's owner's responsibility to verify amount and token id before calling
+    /// lpt lost since 0.3.0 are recorded per account, see list_lpt_lostfound
     #[payable]
     pub fn return_lpt_lostfound(&mut self, account_id: AccountId, amount: U128) -> Promise {
         assert_one_yocto();
         self.assert_owner();
 
         // update inner state
-        let max_amount = self.data().lostfound;
-        require!(amount.0 <= max_amount, E101_INSUFFICIENT_BALANCE);
-        self.data_mut().lostfound -= amount.0;
+        self.internal_sub_lpt_lostfound(&account_id, amount.0);
 
         self.transfer_lpt_lostfound(&account_id, amount.0)
     }
 
     #[payable]
-    pub fn return_removed_proposal_assets(&mut self, account_id: AccountId, token_id: AccountId, amount: U128) -> Promise {
+    pub fn return_removed_proposal_assets(&mut self, account_id: AccountId, token_id: RewardTokenId, amount: U128) -> Promise {
         assert_one_yocto();
         self.assert_owner();
 
@@ -105,6 +226,24 @@ impl Contract {
         self.transfer_removed_proposal_assets(&token_id, &account_id, amount.0)
     }
 
+    /// owner help to return the rewards failed to withdraw after the account is removed.
+    #[payable]
+    pub fn return_reward_lostfound(&mut self, account_id: AccountId, token_id: RewardTokenId, amount: U128) -> Promise {
+        assert_one_yocto();
+        self.assert_owner();
+
+        let key = (account_id.clone(), token_id.clone());
+        let max_amount = self.data().reward_lostfound.get(&key).unwrap_or(0_u128);
+        require!(amount.0 <= max_amount, E101_INSUFFICIENT_BALANCE);
+        if amount.0 == max_amount {
+            self.data_mut().reward_lostfound.remove(&key);
+        } else {
+            self.data_mut().reward_lostfound.insert(&key, &(max_amount - amount.0));
+        }
+
+        self.transfer_reward_lostfound(&token_id, &account_id, amount.0)
+    }
+
     #[private]
     pub fn callback_withdraw_lpt_lostfound(&mut self, receiver_id: AccountId, amount: U128) {
         require!(
@@ -116,7 +255,7 @@ impl Contract {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Failed => {
                 // all seed amount go to lostfound
-                self.data_mut().lostfound += amount;
+                self.internal_add_lpt_lostfound(&receiver_id, amount);
 
                 Event::LptWithdrawLostfound {
                     receiver_id: &receiver_id,
@@ -136,10 +275,39 @@ impl Contract {
         }
     }
 
+    #[private]
+    pub fn callback_withdraw_reward_lostfound(
+        &mut self,
+        token_id: RewardTokenId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let amount: Balance = amount.into();
+        let success = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                self.internal_add_reward_lostfound(&receiver_id, &token_id, amount);
+                false
+            }
+        };
+        Event::RewardWithdrawLostfound {
+            receiver_id: &receiver_id,
+            token_id: &token_id,
+            withdraw_amount: &U128(amount),
+            success,
+        }
+        .emit();
+    }
+
     #[private]
     pub fn callback_removed_proposal_assets(
         &mut self,
-        token_id: AccountId,
+        token_id: RewardTokenId,
         receiver_id: AccountId,
         amount: U128,
     ) {
@@ -199,15 +367,26 @@ impl Contract {
         ))
     }
 
-    fn transfer_removed_proposal_assets(&mut self, token_id: &AccountId, account_id: &AccountId, amount: Balance) -> Promise {
-        ext_fungible_token::ft_transfer(
+    fn transfer_reward_lostfound(&mut self, token_id: &RewardTokenId, account_id: &AccountId, amount: Balance) -> Promise {
+        reward_token_transfer(token_id, account_id, amount, GAS_FOR_REWARD_LOSTFOUND)
+        .then(ext_self::callback_withdraw_reward_lostfound(
+            token_id.clone(),
             account_id.clone(),
             amount.into(),
-            None,
-            token_id.clone(),
-            1,
-            GAS_FOR_REMOVED_PROPOSAL_ASSETS,
-        )
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_REWARD_LOSTFOUND,
+        ))
+    }
+
+    pub fn internal_add_reward_lostfound(&mut self, account_id: &AccountId, token_id: &RewardTokenId, amount: Balance) {
+        let key = (account_id.clone(), token_id.clone());
+        let current_amount = self.data().reward_lostfound.get(&key).unwrap_or(0_u128);
+        self.data_mut().reward_lostfound.insert(&key, &(current_amount + amount));
+    }
+
+    fn transfer_removed_proposal_assets(&mut self, token_id: &RewardTokenId, account_id: &AccountId, amount: Balance) -> Promise {
+        reward_token_transfer(token_id, account_id, amount, GAS_FOR_REMOVED_PROPOSAL_ASSETS)
         .then(ext_self::callback_removed_proposal_assets(
             token_id.clone(),
             account_id.clone(),
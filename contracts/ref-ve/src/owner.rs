@@ -18,6 +18,32 @@ impl Contract {
         self.data_mut().owner_id = owner_id;
     }
 
+    /// Set the account which receives the incentive dust. Only can be called by owner.
+    #[payable]
+    pub fn set_treasury(&mut self, treasury_id: AccountId) {
+        assert_one_yocto();
+        self.assert_owner();
+        self.data_mut().treasury_id = Some(treasury_id);
+    }
+
+    /// Fund the NEAR used to register voters on reward tokens. Only can be called by owner.
+    #[payable]
+    pub fn fund_reward_storage_budget(&mut self) {
+        self.assert_owner();
+        require!(env::attached_deposit() > 0, E101_INSUFFICIENT_BALANCE);
+        self.data_mut().reward_storage_budget += env::attached_deposit();
+    }
+
+    /// Take back unused reward storage budget to owner. Only can be called by owner.
+    #[payable]
+    pub fn withdraw_reward_storage_budget(&mut self, amount: U128) -> Promise {
+        assert_one_yocto();
+        self.assert_owner();
+        require!(amount.0 <= self.data().reward_storage_budget, E310_INSUFFICIENT_STORAGE_BUDGET);
+        self.data_mut().reward_storage_budget -= amount.0;
+        Promise::new(self.data().owner_id.clone()).transfer(amount.0)
+    }
+
     /// Extend operators. Only can be called by owner.
     #[payable]
     pub fn extend_operators(&mut self, operators: Vec<AccountId>) {
@@ -38,6 +64,26 @@ impl Contract {
         }
     }
 
+    /// Extend guardians. Only can be called by owner.
+    #[payable]
+    pub fn extend_guardians(&mut self, guardians: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_owner();
+        for guardian in guardians {
+            self.data_mut().guardians.insert(&guardian);
+        }
+    }
+
+    /// Remove guardians. Only can be called by owner.
+    #[payable]
+    pub fn remove_guardians(&mut self, guardians: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_owner();
+        for guardian in guardians {
+            self.data_mut().guardians.remove(&guardian);
+        }
+    }
+
     /// Should only be called by this contract on migration.
     /// This is NOOP implementation. KEEP IT if you haven't changed contract state.
     /// If you have, you need to implement migration from old state 
@@ -50,8 +96,9 @@ impl Contract {
         // see if ContractData need upgrade
         contract.data = 
         match contract.data {
-            VersionedContractData::V0100(data) => VersionedContractData::V0200(data.into()),
-            VersionedContractData::V0200(data) => VersionedContractData::V0200(data),
+            VersionedContractData::V0100(data) => VersionedContractData::V0300(ContractDataV0200::from(data).into()),
+            VersionedContractData::V0200(data) => VersionedContractData::V0300(data.into()),
+            VersionedContractData::V0300(data) => VersionedContractData::V0300(data),
         };
         contract
     }
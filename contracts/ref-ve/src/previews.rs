@@ -0,0 +1,192 @@
+use crate::*;
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct LockPreview {
+    /// LPT to be locked
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    /// LPT refunded as it's beyond the precision of veLPT
+    #[serde(with = "u128_dec_format")]
+    pub refund: Balance,
+    #[serde(with = "u128_dec_format")]
+    pub increased_ve_lpt: Balance,
+    /// veLPT of the account after the lock
+    #[serde(with = "u128_dec_format")]
+    pub ve_lpt_amount: Balance,
+    #[serde(with = "u64_dec_format")]
+    pub unlock_timestamp: u64,
+    pub duration_sec: u32,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct WithdrawPreview {
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    /// Also the love token burnt
+    #[serde(with = "u128_dec_format")]
+    pub decreased_ve_lpt: Balance,
+    /// veLPT of the account after the withdraw
+    #[serde(with = "u128_dec_format")]
+    pub ve_lpt_amount: Balance,
+    /// LPT left locked
+    #[serde(with = "u128_dec_format")]
+    pub lpt_amount: Balance,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct VoteRewardPreview {
+    /// Ballots of the vote, that is the veLPT of the account
+    #[serde(with = "u128_dec_format")]
+    pub ballots: Balance,
+    /// Rewards if the voting ended right after the vote
+    #[serde(with = "u128_map_format")]
+    pub rewards: HashMap<RewardTokenId, Balance>,
+    pub vesting_rewards: Vec<VestingReward>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct ProjectedReward {
+    pub proposal_id: u32,
+    pub status: Option<ProposalStatus>,
+    /// Ballots of all voters the projection is based on
+    #[serde(with = "u128_dec_format")]
+    pub total_ballots: Balance,
+    #[serde(with = "u128_map_format")]
+    pub rewards: HashMap<RewardTokenId, Balance>,
+    pub vesting_rewards: Vec<VestingReward>,
+}
+
+/// Read-only simulations of the account actions, by the same logic as the actions,
+/// nothing is written. They fail with the same errors as the actions would.
+#[near_bindgen]
+impl Contract {
+    /// Locking amount of LPT for duration_sec, the account could be unregistered.
+    pub fn preview_lock(&self, account_id: AccountId, amount: U128, duration_sec: u32) -> LockPreview {
+        let (amount, refund) = self.real_amount_and_refund(amount.into());
+        require!(amount > 0, E101_INSUFFICIENT_BALANCE);
+        let account = self.internal_get_account(&account_id);
+        let is_registered = account.is_some();
+        let mut account = account.unwrap_or_else(|| Account::new(&account_id, &env::current_account_id()));
+        let increased_ve_lpt = self.internal_apply_lock(&mut account, is_registered, amount, duration_sec);
+        LockPreview {
+            amount,
+            refund,
+            increased_ve_lpt,
+            ve_lpt_amount: account.ve_lpt_amount,
+            unlock_timestamp: account.unlock_timestamp,
+            duration_sec,
+        }
+    }
+
+    /// Appending amount of LPT to the current lock and extending it by append_duration_sec.
+    pub fn preview_append(&self, account_id: AccountId, amount: U128, append_duration_sec: u32) -> LockPreview {
+        let (amount, refund) = self.real_amount_and_refund(amount.into());
+        require!(amount > 0, E101_INSUFFICIENT_BALANCE);
+        let mut account = self.internal_unwrap_account(&account_id);
+        let (increased_ve_lpt, duration_sec) = self.internal_apply_append(&mut account, amount, append_duration_sec);
+        LockPreview {
+            amount,
+            refund,
+            increased_ve_lpt,
+            ve_lpt_amount: account.ve_lpt_amount,
+            unlock_timestamp: account.unlock_timestamp,
+            duration_sec,
+        }
+    }
+
+    /// Withdrawing amount of LPT, all of it when amount is None.
+    pub fn preview_withdraw(&self, account_id: AccountId, amount: Option<U128>) -> WithdrawPreview {
+        let mut account = self.internal_unwrap_account(&account_id);
+        let amount = amount.map(|v| v.0).unwrap_or(account.lpt_amount);
+        let decreased_ve_lpt = account.withdraw_lpt(amount);
+        WithdrawPreview {
+            amount,
+            decreased_ve_lpt,
+            ve_lpt_amount: account.ve_lpt_amount,
+            lpt_amount: account.lpt_amount,
+        }
+    }
+
+    /// Rewards of voting the action with the current veLPT of the account,
+    /// estimated by the ballots and incentives as if voting ended right after the vote.
+    pub fn preview_vote_reward(&self, account_id: AccountId, proposal_id: u32, action: Action) -> VoteRewardPreview {
+        let account = self.internal_unwrap_account(&account_id);
+        require!(account.ve_lpt_amount > 0, E303_INSUFFICIENT_VE_LPT);
+        require!(!account.proposals.contains_key(&proposal_id), E200_ALREADY_VOTED);
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        proposal.append_vote(&action, account.ve_lpt_amount, self.data().cur_total_ve_lpt);
+        let (rewards, vesting_rewards) = proposal.claim_reward(&VoteDetail {
+            action,
+            amount: account.ve_lpt_amount,
+        }).unwrap_or_default();
+        VoteRewardPreview {
+            ballots: account.ve_lpt_amount,
+            rewards: rewards.into_iter().collect(),
+            vesting_rewards,
+        }
+    }
+
+    /// What claim_rewards of every proposal the account voted would return, ordered by proposal id,
+    /// expired ones pay their rewards and cancelled ones are released without rewards.
+    pub fn preview_claim(&self, account_id: AccountId) -> ClaimRewardsResult {
+        let account = self.internal_unwrap_account(&account_id);
+        let mut result = ClaimRewardsResult {
+            claimed_proposal_ids: vec![],
+            rewards: HashMap::new(),
+            vesting_rewards: vec![],
+        };
+        let mut proposal_ids: Vec<u32> = account.proposals.keys().cloned().collect();
+        proposal_ids.sort_unstable();
+        for proposal_id in proposal_ids {
+            let mut proposal = self.internal_unwrap_proposal(proposal_id);
+            match proposal.status {
+                Some(ProposalStatus::Expired) => {
+                    if let Some((rewards, vesting_rewards)) = proposal.claim_reward(&account.proposals[&proposal_id]) {
+                        for (token_id, amount) in rewards {
+                            *result.rewards.entry(token_id).or_insert(0) += amount;
+                        }
+                        result.vesting_rewards.extend(vesting_rewards);
+                    }
+                    result.claimed_proposal_ids.push(proposal_id);
+                }
+                Some(ProposalStatus::Cancelled) => result.claimed_proposal_ids.push(proposal_id),
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// Rewards of each active proposal the account voted if voting ended now,
+    /// by the current ballots and incentives, ordered by proposal id.
+    pub fn get_projected_rewards(&self, account_id: AccountId) -> Vec<ProjectedReward> {
+        let account = self.internal_unwrap_account(&account_id);
+        let mut ret: Vec<ProjectedReward> = account.proposals.iter()
+            .filter_map(|(proposal_id, vote_detail)| {
+                let mut proposal = self.internal_unwrap_proposal(*proposal_id);
+                match proposal.status {
+                    Some(ProposalStatus::Expired) | Some(ProposalStatus::Cancelled) => None,
+                    _ => {
+                        let (rewards, vesting_rewards) = proposal.claim_reward(vote_detail).unwrap_or_default();
+                        Some(ProjectedReward {
+                            proposal_id: *proposal_id,
+                            status: proposal.status.clone(),
+                            total_ballots: proposal.votes.iter().map(|vote| vote.total_ballots).sum(),
+                            rewards: rewards.into_iter().collect(),
+                            vesting_rewards,
+                        })
+                    }
+                }
+            })
+            .collect();
+        ret.sort_by_key(|item| item.proposal_id);
+        ret
+    }
+}
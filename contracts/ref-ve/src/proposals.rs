@@ -13,14 +13,69 @@ pub enum ProposalKind {
     Common,
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, PartialEq)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize))]
+impl ProposalKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProposalKind::FarmingReward { .. } => "FarmingReward",
+            ProposalKind::Poll { .. } => "Poll",
+            ProposalKind::Common => "Common",
+        }
+    }
+}
+
+/// Where the full text of a proposal lives off-chain.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalMetadata {
+    pub title: String,
+    pub discussion_link: Option<String>,
+    /// Such as an ipfs or https uri of the full text.
+    pub content_uri: Option<String>,
+    /// Hex encoded sha256 of the full text, in lowercase.
+    pub content_hash: Option<String>,
+}
+
+impl ProposalMetadata {
+    /// Checks the length of each field and the format of content_hash,
+    /// content_hash is turned into lowercase.
+    pub fn validate(mut self) -> Self {
+        require!(self.title.len() <= METADATA_TITLE_LIMIT, E215_METADATA_TOO_LONG);
+        for uri in [&self.discussion_link, &self.content_uri].iter().filter_map(|v| v.as_ref()) {
+            require!(uri.len() <= METADATA_URI_LIMIT, E215_METADATA_TOO_LONG);
+        }
+        if let Some(content_hash) = self.content_hash.as_mut() {
+            require!(
+                content_hash.len() == 64 && content_hash.chars().all(|c| c.is_ascii_hexdigit()),
+                E216_INVALID_CONTENT_HASH
+            );
+            content_hash.make_ascii_lowercase();
+        }
+        self
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 #[serde(crate = "near_sdk::serde")]
 pub enum ProposalStatus {
     WarmUp,
     InProgress,
     /// Expired after period of time.
     Expired,
+    /// Cancelled by a guardian, no rewards for the voters.
+    Cancelled,
+}
+
+/// Who cancelled the proposal and why.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize, PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub struct Cancellation {
+    pub guardian_id: AccountId,
+    pub reason: String,
+    #[serde(with = "u64_dec_format")]
+    pub cancelled_at: Timestamp,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, Default)]
@@ -38,6 +93,9 @@ pub struct VoteInfo {
 pub struct Proposal {
     pub id: u32,
     pub description: String,
+    pub metadata: Option<ProposalMetadata>,
+    /// Categories of the proposal, lowercase.
+    pub tags: Vec<String>,
     /// Original proposer.
     pub proposer: AccountId,
     /// Kind of proposal with relevant information.
@@ -59,21 +117,65 @@ pub struct Proposal {
 
     /// Incentive of proposal with relevant information.   
     pub incentive: HashMap<u32, ProposalIncentive>,
+    /// The number of voters who have claimed their reward.
+    #[serde(with = "u64_dec_format")]
+    pub claimed_count: u64,
+    /// Claims made before version 0.3.0 are not counted in claimed_count.
+    pub has_untracked_claims: bool,
+    /// Whether the incentive dust has been swept to treasury.
+    pub is_settled: bool,
+    /// NEAR paid by the proposer for the storage of the proposal,
+    /// refunded when the proposal is removed or archived.
+    #[serde(with = "u128_dec_format")]
+    pub storage_deposit: Balance,
+    /// Times the proposal has been updated during WarmUp.
+    pub revision: u32,
+    pub cancellation: Option<Cancellation>,
     #[borsh_skip]
     pub status: Option<ProposalStatus>,
     #[borsh_skip] 
     pub is_nonsense: Option<bool>,
 }
 
+/// What is kept of a settled proposal after archived.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize, PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalSummary {
+    pub id: u32,
+    /// Name of the ProposalKind.
+    pub kind: String,
+    pub proposer: AccountId,
+    pub metadata: Option<ProposalMetadata>,
+    /// Final votes.
+    pub votes: Vec<VoteInfo>,
+    #[serde(with = "u64_dec_format")]
+    pub participants: u64,
+    #[serde(with = "u64_dec_format")]
+    pub start_at: Timestamp,
+    #[serde(with = "u64_dec_format")]
+    pub end_at: Timestamp,
+    /// Index of the vote with the most ballots, None if nobody voted.
+    pub outcome: Option<u32>,
+    pub is_nonsense: Option<bool>,
+    /// sha256 of the borsh serialized proposal when archived.
+    pub content_hash: Base58CryptoHash,
+}
+
 #[derive(BorshSerialize, BorshDeserialize)]
 pub enum VProposal {
+    V0200(ProposalV0200),
     Current(Proposal),
+    Archived(ProposalSummary),
 }
 
-impl From<VProposal> for Proposal {
-    fn from(v: VProposal) -> Self {
-        match v {
-            VProposal::Current(c) => c,
+impl VProposal {
+    /// None if the proposal has been archived.
+    pub fn into_proposal(self) -> Option<Proposal> {
+        match self {
+            VProposal::V0200(c) => Some(c.into()),
+            VProposal::Current(c) => Some(c),
+            VProposal::Archived(_) => None,
         }
     }
 }
@@ -98,7 +200,9 @@ impl Proposal {
 
     pub fn update_status(&mut self) {
         let now = env::block_timestamp(); 
-        if now < self.start_at {
+        if self.cancellation.is_some() {
+            self.status = Some(ProposalStatus::Cancelled);
+        } else if now < self.start_at {
             self.status = Some(ProposalStatus::WarmUp);
         } else if now >= self.start_at && now < self.end_at {
             self.status = Some(ProposalStatus::InProgress);
@@ -107,18 +211,80 @@ impl Proposal {
             self.update_result();
         }
     }
+
+    pub fn to_summary(&self) -> ProposalSummary {
+        let mut outcome = None;
+        let mut max_ballots = 0;
+        for (index, vote) in self.votes.iter().enumerate() {
+            if vote.total_ballots > max_ballots {
+                max_ballots = vote.total_ballots;
+                outcome = Some(index as u32);
+            }
+        }
+        ProposalSummary {
+            id: self.id,
+            kind: self.kind.name().to_string(),
+            proposer: self.proposer.clone(),
+            metadata: self.metadata.clone(),
+            votes: self.votes.clone(),
+            participants: self.participants,
+            start_at: self.start_at,
+            end_at: self.end_at,
+            outcome,
+            is_nonsense: self.is_nonsense,
+            content_hash: env::sha256_array(&self.try_to_vec().unwrap()).into(),
+        }
+    }
 }
 
 
 impl Contract {
     pub fn internal_unwrap_proposal(&self, proposal_id: u32) -> Proposal {
-        let mut proposal = self.internal_get_proposal(proposal_id).expect(E404_PROPOSAL_NOT_EXIST);
+        let mut proposal = self.data().proposals.get(&proposal_id)
+            .expect(E404_PROPOSAL_NOT_EXIST)
+            .into_proposal()
+            .expect(E411_PROPOSAL_ARCHIVED);
         proposal.update_status();
         proposal
     }
 
+    /// None if the proposal doesn't exist or has been archived.
     pub fn internal_get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
-        self.data().proposals.get(&proposal_id).map(|o| o.into())
+        self.data().proposals.get(&proposal_id).and_then(|o| o.into_proposal())
+    }
+
+    /// Replaces a settled proposal which every voter has claimed, or a cancelled one
+    /// which every voter is released from, with its summary,
+    /// the incentive deposit ledgers are cleared as well.
+    pub fn internal_archive_proposal(&mut self, proposal_id: u32) -> ProposalSummary {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        match proposal.status {
+            Some(ProposalStatus::Expired) => {
+                if !proposal.is_settled {
+                    // settled here when there is no dust to sweep to treasury
+                    require!(self.internal_take_incentive_dust(&mut proposal).is_empty(), E410_PROPOSAL_NOT_SETTLED);
+                }
+            }
+            // the incentives were refunded on cancellation
+            Some(ProposalStatus::Cancelled) => {}
+            _ => env::panic_str(E409_PROPOSAL_NOT_EXPIRED),
+        }
+        require!(proposal.claimed_count >= proposal.participants, E212_REWARD_NOT_ALL_CLAIMED);
+        for incentive_key in proposal.incentive.keys() {
+            if let Some(mut deposits) = self.data_mut().incentive_deposits.remove(&(proposal_id, *incentive_key)) {
+                deposits.clear();
+            }
+        }
+        let summary = proposal.to_summary();
+        let prev_storage = env::storage_usage();
+        self.data_mut().proposals.insert(&proposal_id, &VProposal::Archived(summary.clone()));
+        // the summary and index entries stay, so only the bytes released are refunded
+        let released = prev_storage.saturating_sub(env::storage_usage()) as Balance * env::storage_byte_cost();
+        let refund = std::cmp::min(released, proposal.storage_deposit);
+        if refund > 0 {
+            Promise::new(proposal.proposer).transfer(refund);
+        }
+        summary
     }
 
     pub fn internal_set_proposal(&mut self, proposal_id: u32, proposal: Proposal) {
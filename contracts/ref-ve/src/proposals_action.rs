@@ -70,38 +70,42 @@ impl Proposal {
             self.votes[index].total_ballots -= amount;
         }
     }
-}
 
-impl Contract {
-    pub fn internal_append_vote(
-        &mut self,
-        proposal_id: u32,
-        action: &Action,
-        amount: Balance,
-    ) {
-        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+    /// Counts a new vote of amount ballots, the proposal must be in progress.
+    pub fn append_vote(&mut self, action: &Action, amount: Balance, cur_total_ve_lpt: Balance) {
         require!(action != &Action::VoteNonsense, E201_INVALID_VOTE);
         
         // check proposal is inprogress
-        match proposal.status {
+        match self.status {
             Some(ProposalStatus::InProgress) => {
                 // update proposal result
-                proposal.update_votes(
+                self.update_votes(
                     action,
                     amount,
                     true
                 );
-                proposal.ve_amount_at_last_action = self.data().cur_total_ve_lpt;
-                proposal.votes[action.get_index()].participants += 1;
-                proposal.participants += 1;
-                
-                self.data_mut()
-                    .proposals
-                    .insert(&proposal_id, &proposal.into());
+                self.ve_amount_at_last_action = cur_total_ve_lpt;
+                self.votes[action.get_index()].participants += 1;
+                self.participants += 1;
             },
             _ => env::panic_str(E205_NOT_VOTABLE)
         }
     }
+}
+
+impl Contract {
+    pub fn internal_append_vote(
+        &mut self,
+        proposal_id: u32,
+        action: &Action,
+        amount: Balance,
+    ) {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        proposal.append_vote(action, amount, self.data().cur_total_ve_lpt);
+        self.data_mut()
+            .proposals
+            .insert(&proposal_id, &proposal.into());
+    }
 
     pub fn internal_cancel_vote(
         &mut self,
@@ -1,17 +1,23 @@
 use crate::*;
+
+/// Rewards can be withdrawn right away and the ones vesting, claimed from one proposal.
+pub type ClaimedReward = (Vec<(RewardTokenId, Balance)>, Vec<VestingReward>);
+
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, PartialEq)]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize))]
 #[serde(crate = "near_sdk::serde")]
 pub struct ProposalIncentive{
-    pub incentive_token_ids: Vec<AccountId>,
+    pub incentive_token_ids: Vec<RewardTokenId>,
     #[serde(with = "u128_vec_format")]
     pub incentive_amounts: Vec<Balance>,
     #[serde(with = "u128_vec_format")]
     pub claimed_amounts: Vec<Balance>,
+    /// Claimed rewards vest linearly over this period after end_at, 0 means no vesting.
+    pub vesting_duration_sec: u32,
 }
 
 impl ProposalIncentive {
-    pub fn calc_reward(&self, account_votes_amount: u128, votes_total_amount: Balance) -> Vec<(AccountId, Balance)> {
+    pub fn calc_reward(&self, account_votes_amount: u128, votes_total_amount: Balance) -> Vec<(RewardTokenId, Balance)> {
         let mut claimed_list = vec![];
         for (index, token_id) in self.incentive_token_ids.iter().enumerate() {
             let reward = u128_ratio(self.incentive_amounts[index], account_votes_amount, votes_total_amount);
@@ -23,39 +29,78 @@ impl ProposalIncentive {
 
 impl Proposal {
     
+    /// Incentive keys a vote shares, the voted farm for FarmingReward,
+    /// otherwise key 0 shared by all voters and key index + 1 by voters of that option.
+    pub fn get_incentive_keys_for_reward_calc(&self, action: &Action) -> Vec<u32> {
+        match self.kind {
+            ProposalKind::FarmingReward { .. } => vec![action.get_index() as u32],
+            _ => vec![0, action.get_index() as u32 + 1],
+        }
+    }
+
     pub fn get_votes_total_amount_for_reward_calc(&self, incentive_key: u32) -> u128{
         match self.kind{
             ProposalKind::FarmingReward { .. } => {
                 self.votes[incentive_key as usize].total_ballots
             },
-            _ => {
+            _ if incentive_key == 0 => {
                 self.votes.iter().map(|item| item.total_ballots).sum()
+            },
+            _ => {
+                self.votes[incentive_key as usize - 1].total_ballots
             }
         }
     }
 
-    pub fn claim_reward(&mut self, vote_detail: &VoteDetail) -> Option<Vec<(AccountId, Balance)>> {
-        let incentive_key = if let ProposalKind::FarmingReward { .. } = self.kind {
-            vote_detail.action.get_index() as u32
-        } else {
-            0
-        };
-        let votes_total_amount = self.get_votes_total_amount_for_reward_calc(incentive_key);
-        if let Some(incentive) = self.incentive.get_mut(&incentive_key) {
-            let res = incentive.calc_reward(vote_detail.amount, votes_total_amount);
-            incentive.claimed_amounts = res.iter().zip(incentive.claimed_amounts.iter()).map(|(new, old)| new.1 + old).collect();
-            Some(res)
-        } else {
-            None
+    pub fn get_participants_for_reward_calc(&self, incentive_key: u32) -> u64 {
+        match self.kind {
+            ProposalKind::FarmingReward { .. } => self.votes[incentive_key as usize].participants,
+            _ if incentive_key == 0 => self.participants,
+            _ => self.votes[incentive_key as usize - 1].participants,
+        }
+    }
+
+    /// Claims the reward of a voter, rewards of a vesting incentive vest from end_at.
+    pub fn claim_reward(&mut self, vote_detail: &VoteDetail) -> Option<ClaimedReward> {
+        self.claimed_count += 1;
+        let end_at = self.end_at;
+        let mut claimed: Option<ClaimedReward> = None;
+        for incentive_key in self.get_incentive_keys_for_reward_calc(&vote_detail.action) {
+            let votes_total_amount = self.get_votes_total_amount_for_reward_calc(incentive_key);
+            if let Some(incentive) = self.incentive.get_mut(&incentive_key) {
+                let res: Vec<(RewardTokenId, Balance)> = incentive.calc_reward(vote_detail.amount, votes_total_amount)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (token_id, reward))| {
+                        // never pay out more than what is left, in case the dust has been swept
+                        (token_id, std::cmp::min(reward, incentive.incentive_amounts[index] - incentive.claimed_amounts[index]))
+                    })
+                    .collect();
+                incentive.claimed_amounts = res.iter().zip(incentive.claimed_amounts.iter()).map(|(new, old)| new.1 + old).collect();
+                let (rewards, vesting_rewards) = claimed.get_or_insert_with(|| (vec![], vec![]));
+                for (token_id, amount) in res {
+                    if incentive.vesting_duration_sec > 0 {
+                        vesting_rewards.push(VestingReward::new(token_id, amount, end_at, incentive.vesting_duration_sec));
+                    } else if let Some(item) = rewards.iter_mut().find(|item| item.0 == token_id) {
+                        item.1 += amount;
+                    } else {
+                        rewards.push((token_id, amount));
+                    }
+                }
+            }
         }
+        claimed
     }
 
-    pub fn deposit_reward(&mut self, incentive_key: u32, token_id: &AccountId, amount: Balance) -> Balance {
+    pub fn deposit_reward(&mut self, incentive_key: u32, token_id: &RewardTokenId, amount: Balance, vesting_duration_sec: u32) -> Balance {
         let proposal_incentive = self.incentive.entry(incentive_key).or_insert(ProposalIncentive{
             incentive_token_ids: vec![token_id.clone()],
             incentive_amounts: vec![0u128],
             claimed_amounts: vec![0u128],
+            vesting_duration_sec,
         });
+        // the first deposit decides the vesting of the incentive
+        require!(proposal_incentive.vesting_duration_sec == vesting_duration_sec, E214_INVALID_VESTING_DURATION);
         let index = match proposal_incentive.incentive_token_ids.iter().position(|incentive_token_id| incentive_token_id == token_id){
             Some(index) => index,
             None => {
@@ -68,10 +113,26 @@ impl Proposal {
         proposal_incentive.incentive_amounts[index] += amount;
         proposal_incentive.incentive_amounts[index]
     }
+
+    /// Whether every voter of this expired proposal has claimed.
+    /// For claims made before version 0.3.0, what's left of each incentive 
+    /// must be less than the number of its voters, that is only rounding dust.
+    pub fn is_all_claimed(&self) -> bool {
+        if self.claimed_count >= self.participants {
+            return true;
+        }
+        self.has_untracked_claims && self.incentive.iter().all(|(incentive_key, incentive)| {
+            let participants = self.get_participants_for_reward_calc(*incentive_key);
+            // nobody could claim an incentive without voters
+            participants == 0 || incentive.incentive_amounts.iter().zip(incentive.claimed_amounts.iter())
+                .all(|(amount, claimed)| amount - claimed < participants as u128)
+        })
+    }
 }
 
 impl Contract {
-    pub fn internal_deposit_reward(&mut self, proposal_id: u32, incentive_key: u32, token_id: &AccountId, amount: Balance) -> (Balance, Timestamp) {
+    pub fn internal_deposit_reward(&mut self, proposal_id: u32, incentive_key: u32, token_id: &RewardTokenId, amount: Balance, sender_id: &AccountId, vesting_duration_sec: u32) -> (Balance, Timestamp) {
+        require!(vesting_duration_sec <= self.internal_config().max_locking_duration_sec, E214_INVALID_VESTING_DURATION);
         let mut proposal = self.internal_unwrap_proposal(proposal_id);
         match proposal.status {
             Some(ProposalStatus::WarmUp) | Some(ProposalStatus::InProgress) => {
@@ -80,18 +141,19 @@ impl Contract {
                         require!(incentive_key < farm_list.len() as u32, E207_INVALID_INCENTIVE_KEY);
                         let farm_tokens = extra_incentive_tokens(farm_list[incentive_key as usize].clone());
                         require!(
-                            self.data().whitelisted_incentive_tokens.contains(token_id) || farm_tokens.contains(token_id)
+                            self.data().whitelisted_incentive_tokens.contains(token_id) || farm_tokens.iter().any(|farm_token| farm_token.as_str() == token_id)
                             , E203_INVALID_INCENTIVE_TOKEN);
                     },
                     _ => {
-                        require!(incentive_key == 0, E207_INVALID_INCENTIVE_KEY);
+                        require!(incentive_key <= proposal.votes.len() as u32, E207_INVALID_INCENTIVE_KEY);
                         require!(self.data().whitelisted_incentive_tokens.contains(token_id), E203_INVALID_INCENTIVE_TOKEN);
                     },
                 }
                 
-                let total_reward = proposal.deposit_reward(incentive_key, token_id, amount);
+                let total_reward = proposal.deposit_reward(incentive_key, token_id, amount, vesting_duration_sec);
                 let start_at = proposal.start_at;
                 self.internal_set_proposal(proposal_id, proposal.into());
+                self.internal_add_incentive_deposit((proposal_id, incentive_key), token_id, sender_id, amount);
                 (total_reward, start_at)
             },
             _ => {
@@ -99,4 +161,131 @@ impl Contract {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Deposited amount of each (token_id, depositor) of an incentive, 
+    /// kept outside of the proposal as it grows with depositors.
+    pub fn internal_get_incentive_deposits(&self, incentive_id: IncentiveId) -> Option<UnorderedMap<(RewardTokenId, AccountId), Balance>> {
+        self.data().incentive_deposits.get(&incentive_id)
+    }
+
+    pub fn internal_add_incentive_deposit(&mut self, incentive_id: IncentiveId, token_id: &RewardTokenId, depositor: &AccountId, amount: Balance) {
+        let mut deposits = self.internal_get_incentive_deposits(incentive_id).unwrap_or_else(|| {
+            UnorderedMap::new(StorageKeys::IncentiveDepositsItem { proposal_id: incentive_id.0, incentive_key: incentive_id.1 })
+        });
+        let key = (token_id.clone(), depositor.clone());
+        let prev = deposits.get(&key).unwrap_or(0);
+        deposits.insert(&key, &(prev + amount));
+        self.data_mut().incentive_deposits.insert(&incentive_id, &deposits);
+    }
+
+    /// Credits the incentives of a removed proposal back to their depositors.
+    pub fn internal_refund_removed_incentive(&mut self, proposal: &Proposal) {
+        for (incentive_key, incentive) in proposal.incentive.iter() {
+            self.internal_refund_incentive((proposal.id, *incentive_key), incentive);
+        }
+    }
+
+    /// Credits what's unclaimed of an incentive back to its depositors,
+    /// what's not covered by depositor records goes to removed_proposal_assets.
+    pub fn internal_refund_incentive(&mut self, incentive_id: IncentiveId, incentive: &ProposalIncentive) {
+        let mut remaining: Vec<Balance> = incentive.incentive_amounts.iter().zip(incentive.claimed_amounts.iter())
+            .map(|(amount, claimed)| amount - claimed)
+            .collect();
+        if let Some(mut deposits) = self.data_mut().incentive_deposits.remove(&incentive_id) {
+            for ((token_id, depositor), amount) in deposits.iter() {
+                if let Some(index) = incentive.incentive_token_ids.iter().position(|item| item == &token_id) {
+                    let amount = std::cmp::min(amount, remaining[index]);
+                    remaining[index] -= amount;
+                    self.internal_add_incentive_refund(&depositor, &token_id, amount);
+                }
+            }
+            deposits.clear();
+        }
+        for (index, token_id) in incentive.incentive_token_ids.iter().enumerate() {
+            if remaining[index] > 0 {
+                let current_amount = self.data().removed_proposal_assets.get(token_id).unwrap_or(0_u128);
+                self.data_mut().removed_proposal_assets.insert(token_id, &(remaining[index] + current_amount));
+            }
+        }
+    }
+
+    pub fn internal_add_removed_proposal_assets(&mut self, token_id: &RewardTokenId, amount: Balance) {
+        let current_amount = self.data().removed_proposal_assets.get(token_id).unwrap_or(0_u128);
+        self.data_mut().removed_proposal_assets.insert(token_id, &(amount + current_amount));
+    }
+
+    pub fn internal_add_incentive_refund(&mut self, account_id: &AccountId, token_id: &RewardTokenId, amount: Balance) {
+        if amount == 0 {
+            return;
+        }
+        let mut refunds = self.data().incentive_refunds.get(account_id).unwrap_or_default();
+        *refunds.entry(token_id.clone()).or_insert(0) += amount;
+        self.data_mut().incentive_refunds.insert(account_id, &refunds);
+    }
+
+    /// Takes back the deposits of an incentive which no voter could claim,
+    /// as many as the prepaid gas allows, the rest can be taken by next call.
+    /// returns the (token_id, depositor, amount) to be refunded.
+    pub fn internal_take_unused_incentive(&mut self, proposal: &mut Proposal, incentive_key: u32) -> Vec<(RewardTokenId, AccountId, Balance)> {
+        require!(proposal.incentive.contains_key(&incentive_key), E207_INVALID_INCENTIVE_KEY);
+        require!(proposal.get_votes_total_amount_for_reward_calc(incentive_key) == 0, E209_INCENTIVE_STILL_CLAIMABLE);
+        let incentive_id = (proposal.id, incentive_key);
+        let mut deposits = self.internal_get_incentive_deposits(incentive_id).expect(E210_NOTHING_TO_REFUND);
+        require!(!deposits.is_empty(), E210_NOTHING_TO_REFUND);
+
+        let incentive = proposal.incentive.get_mut(&incentive_key).unwrap();
+        let gas_per_refund = GAS_FOR_INCENTIVE_REFUND.0 + GAS_FOR_RESOLVE_INCENTIVE_REFUND.0;
+        let mut refunds = vec![];
+        while !deposits.is_empty() 
+            && env::prepaid_gas().0 - env::used_gas().0 > gas_per_refund * (refunds.len() as u64 + 1) + GAS_FOR_INCENTIVE_REFUND_FINISH.0 {
+            let (token_id, depositor) = deposits.keys_as_vector().get(deposits.len() - 1).unwrap();
+            let amount = deposits.remove(&(token_id.clone(), depositor.clone())).unwrap();
+            if let Some(index) = incentive.incentive_token_ids.iter().position(|item| item == &token_id) {
+                let amount = std::cmp::min(amount, incentive.incentive_amounts[index] - incentive.claimed_amounts[index]);
+                if amount > 0 {
+                    incentive.claimed_amounts[index] += amount;
+                    refunds.push((token_id, depositor, amount));
+                }
+            }
+        }
+        self.data_mut().incentive_deposits.insert(&incentive_id, &deposits);
+        refunds
+    }
+
+    /// Takes the rounding dust left by calc_reward once every voter has claimed,
+    /// returns the (incentive_key, token_id, amount) to be swept, the proposal would be settled.
+    /// Only what's left under the number of voters of an incentive is dust, anything more,
+    /// such as deposits made before 0.3.0 that nobody could claim, goes to removed_proposal_assets.
+    pub fn internal_take_incentive_dust(&mut self, proposal: &mut Proposal) -> Vec<(u32, RewardTokenId, Balance)> {
+        require!(!proposal.is_settled, E211_PROPOSAL_SETTLED);
+        require!(proposal.is_all_claimed(), E212_REWARD_NOT_ALL_CLAIMED);
+        for incentive_key in proposal.incentive.keys() {
+            // deposits nobody could claim belong to their depositors
+            if proposal.get_votes_total_amount_for_reward_calc(*incentive_key) == 0 {
+                require!(
+                    self.internal_get_incentive_deposits((proposal.id, *incentive_key)).map(|deposits| deposits.is_empty()).unwrap_or(true),
+                    E213_UNUSED_INCENTIVE_NOT_REFUNDED
+                );
+            }
+        }
+        let participants: HashMap<u32, u64> = proposal.incentive.keys()
+            .map(|incentive_key| (*incentive_key, proposal.get_participants_for_reward_calc(*incentive_key)))
+            .collect();
+        let mut dust = vec![];
+        for (incentive_key, incentive) in proposal.incentive.iter_mut() {
+            for (index, token_id) in incentive.incentive_token_ids.iter().enumerate() {
+                let amount = incentive.incentive_amounts[index] - incentive.claimed_amounts[index];
+                if amount > 0 {
+                    incentive.claimed_amounts[index] += amount;
+                    if amount < participants[incentive_key] as u128 {
+                        dust.push((*incentive_key, token_id.clone(), amount));
+                    } else {
+                        self.internal_add_removed_proposal_assets(token_id, amount);
+                    }
+                }
+            }
+        }
+        proposal.is_settled = true;
+        dust
+    }
+}
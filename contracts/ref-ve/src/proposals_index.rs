@@ -0,0 +1,151 @@
+use crate::*;
+
+/// Secondary indexes of proposals, each keeps proposal ids ordered.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub enum ProposalIndexKey {
+    Proposer(AccountId),
+    Kind(String),
+    Tag(String),
+}
+
+/// Conditions of list_proposals_by_filter, None matches all.
+#[derive(Serialize, Deserialize, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Clone))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalFilter {
+    pub status: Option<ProposalStatus>,
+    /// Name of the ProposalKind, such as "Poll".
+    pub kind: Option<String>,
+    pub proposer: Option<AccountId>,
+    pub tag: Option<String>,
+}
+
+impl ProposalFilter {
+    pub fn is_match(&self, proposal: &Proposal) -> bool {
+        self.status.as_ref().map(|status| proposal.status.as_ref() == Some(status)).unwrap_or(true)
+            && self.kind.as_ref().map(|kind| proposal.kind.name() == kind).unwrap_or(true)
+            && self.proposer.as_ref().map(|proposer| &proposal.proposer == proposer).unwrap_or(true)
+            && self.tag.as_ref().map(|tag| proposal.tags.contains(tag)).unwrap_or(true)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct ProposalPage {
+    /// Newest first.
+    pub proposals: Vec<Proposal>,
+    /// Pass as from_proposal_id to get the next page, None if there are no more.
+    pub next_cursor: Option<u32>,
+}
+
+/// Tags are lowercased and deduplicated, each is 1 to TAG_LIMIT bytes of letters, digits and '-'.
+pub fn validate_tags(tags: Vec<String>) -> Vec<String> {
+    let mut result: Vec<String> = vec![];
+    for mut tag in tags {
+        require!(
+            !tag.is_empty() && tag.len() <= TAG_LIMIT && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+            E416_INVALID_TAGS
+        );
+        tag.make_ascii_lowercase();
+        if !result.contains(&tag) {
+            result.push(tag);
+        }
+    }
+    require!(result.len() <= MAX_PROPOSAL_TAGS, E416_INVALID_TAGS);
+    result
+}
+
+fn proposal_index_keys(proposal: &Proposal) -> Vec<ProposalIndexKey> {
+    let mut keys = vec![
+        ProposalIndexKey::Proposer(proposal.proposer.clone()),
+        ProposalIndexKey::Kind(proposal.kind.name().to_string()),
+    ];
+    keys.extend(proposal.tags.iter().map(|tag| ProposalIndexKey::Tag(tag.clone())));
+    keys
+}
+
+impl Contract {
+    /// Returns false if the proposal was indexed already.
+    /// Ids of archived proposals are left in the indexes and skipped when listing.
+    pub fn internal_index_proposal(&mut self, proposal: &Proposal) -> bool {
+        let mut is_new = false;
+        for key in proposal_index_keys(proposal) {
+            let mut ids = self.data().proposal_index.get(&key).unwrap_or_else(|| {
+                TreeMap::new(StorageKeys::ProposalIndexItem { key: key.try_to_vec().unwrap() })
+            });
+            is_new |= ids.insert(&proposal.id, &()).is_none();
+            self.data_mut().proposal_index.insert(&key, &ids);
+        }
+        is_new
+    }
+
+    pub fn internal_unindex_proposal(&mut self, proposal: &Proposal) {
+        for key in proposal_index_keys(proposal) {
+            if let Some(mut ids) = self.data().proposal_index.get(&key) {
+                ids.remove(&proposal.id);
+                if ids.is_empty() {
+                    self.data_mut().proposal_index.remove(&key);
+                } else {
+                    self.data_mut().proposal_index.insert(&key, &ids);
+                }
+            }
+        }
+    }
+
+    /// Indexes at most limit proposals from from_proposal_id, for those created before the indexes exist.
+    /// Returns the number of proposals newly indexed.
+    pub fn internal_index_proposals(&mut self, from_proposal_id: u32, limit: u32) -> u32 {
+        let to_proposal_id = std::cmp::min(from_proposal_id.saturating_add(limit), self.data().last_proposal_id);
+        let mut count = 0;
+        for proposal_id in from_proposal_id..to_proposal_id {
+            if let Some(proposal) = self.internal_get_proposal(proposal_id) {
+                if self.internal_index_proposal(&proposal) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Walks proposal ids from from_proposal_id in descending order through the most selective index,
+    /// at most PROPOSAL_SCAN_LIMIT ids are checked in one call.
+    pub fn internal_list_proposals_by_filter(&self, filter: &ProposalFilter, from_proposal_id: Option<u32>, limit: u32) -> ProposalPage {
+        // ids below upper are listed
+        let upper = std::cmp::min(
+            from_proposal_id.map(|id| id.saturating_add(1)).unwrap_or(u32::MAX),
+            self.data().last_proposal_id
+        );
+        let index_key = if let Some(proposer) = &filter.proposer {
+            Some(ProposalIndexKey::Proposer(proposer.clone()))
+        } else if let Some(tag) = &filter.tag {
+            Some(ProposalIndexKey::Tag(tag.to_ascii_lowercase()))
+        } else {
+            filter.kind.as_ref().map(|kind| ProposalIndexKey::Kind(kind.clone()))
+        };
+        let index = index_key.map(|key| self.data().proposal_index.get(&key));
+        let ids: Box<dyn Iterator<Item = u32> + '_> = match &index {
+            Some(Some(ids)) => Box::new(ids.iter_rev_from(upper).map(|(id, _)| id)),
+            Some(None) => Box::new(std::iter::empty()),
+            None => Box::new((0..upper).rev()),
+        };
+
+        let mut page = ProposalPage {
+            proposals: vec![],
+            next_cursor: None,
+        };
+        for (scanned, proposal_id) in ids.enumerate() {
+            if page.proposals.len() as u32 >= limit || scanned >= PROPOSAL_SCAN_LIMIT {
+                page.next_cursor = Some(proposal_id);
+                break;
+            }
+            if let Some(mut proposal) = self.internal_get_proposal(proposal_id) {
+                proposal.update_status();
+                if filter.is_match(&proposal) {
+                    page.proposals.push(proposal);
+                }
+            }
+        }
+        page
+    }
+}
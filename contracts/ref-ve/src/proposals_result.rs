@@ -0,0 +1,135 @@
+use crate::*;
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug, PartialEq))]
+pub enum ProposalOutcome {
+    /// Voting is not over yet.
+    Pending,
+    Cancelled,
+    /// Voting is over and nobody voted.
+    NoVotes,
+    /// Common proposal with more approve than reject ballots.
+    Approved,
+    Rejected,
+    /// Common proposal with more nonsense ballots than approve and reject together.
+    Nonsense,
+    /// Poll with a single top option.
+    Selected { option: u32 },
+    /// Poll with several top options of the same ballots.
+    Tied { options: Vec<u32> },
+    /// FarmingReward proposal, total_reward is allocated by farm_reward of each option.
+    Allocated,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct OptionResult {
+    /// Approve, Reject and Nonsense for a Common proposal, the option or farm otherwise.
+    pub name: String,
+    #[serde(with = "u128_dec_format")]
+    pub ballots: u128,
+    /// Share of the ballots of all options.
+    pub share_bps: u32,
+    pub participants: u64,
+    /// The part of total_reward for the farm, FarmingReward only.
+    pub farm_reward: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct ProposalResult {
+    pub proposal_id: u32,
+    pub status: Option<ProposalStatus>,
+    pub options: Vec<OptionResult>,
+    /// Option indexes by ballots descending, ties keep the option order.
+    pub ranking: Vec<u32>,
+    /// The option with the most ballots, None if nobody voted or tied.
+    pub winner: Option<u32>,
+    #[serde(with = "u128_dec_format")]
+    pub total_ballots: u128,
+    #[serde(with = "u128_dec_format")]
+    pub ve_amount_at_last_action: u128,
+    /// total_ballots against ve_amount_at_last_action.
+    pub turnout_bps: u32,
+    #[serde(with = "u64_dec_format")]
+    pub participants: u64,
+    pub outcome: ProposalOutcome,
+}
+
+impl Proposal {
+    /// Needs the status updated.
+    pub fn to_result(&self) -> ProposalResult {
+        let total_ballots: u128 = self.votes.iter().map(|vote| vote.total_ballots).sum();
+        let bps = |amount: u128, total: u128| {
+            if total == 0 { 0 } else { u128_ratio(amount, FEE_DIVISOR as u128, total) as u32 }
+        };
+        let names: Vec<String> = match &self.kind {
+            ProposalKind::FarmingReward { farm_list, .. } => farm_list.clone(),
+            ProposalKind::Poll { options } => options.clone(),
+            ProposalKind::Common => vec!["Approve".to_string(), "Reject".to_string(), "Nonsense".to_string()],
+        };
+        let options = self.votes.iter().zip(names)
+            .map(|(vote, name)| OptionResult {
+                name,
+                ballots: vote.total_ballots,
+                share_bps: bps(vote.total_ballots, total_ballots),
+                participants: vote.participants,
+                farm_reward: match &self.kind {
+                    ProposalKind::FarmingReward { total_reward, .. } if total_ballots > 0 => {
+                        Some(u128_ratio(*total_reward as u128, vote.total_ballots, total_ballots) as u32)
+                    }
+                    ProposalKind::FarmingReward { .. } => Some(0),
+                    _ => None,
+                },
+            })
+            .collect();
+
+        let mut ranking: Vec<u32> = (0..self.votes.len() as u32).collect();
+        ranking.sort_by(|a, b| self.votes[*b as usize].total_ballots.cmp(&self.votes[*a as usize].total_ballots));
+        let top_ballots = ranking.first().map(|index| self.votes[*index as usize].total_ballots).unwrap_or(0);
+        let top: Vec<u32> = ranking.iter()
+            .filter(|index| self.votes[**index as usize].total_ballots == top_ballots)
+            .cloned()
+            .collect();
+        let winner = if top_ballots > 0 && top.len() == 1 { Some(top[0]) } else { None };
+
+        let outcome = match self.status {
+            Some(ProposalStatus::Cancelled) => ProposalOutcome::Cancelled,
+            Some(ProposalStatus::Expired) if total_ballots == 0 => ProposalOutcome::NoVotes,
+            Some(ProposalStatus::Expired) => match &self.kind {
+                ProposalKind::Common => {
+                    let votes = &self.votes;
+                    if self.is_nonsense == Some(true) {
+                        ProposalOutcome::Nonsense
+                    } else if votes[Vote::Approve as usize].total_ballots > votes[Vote::Reject as usize].total_ballots {
+                        ProposalOutcome::Approved
+                    } else {
+                        ProposalOutcome::Rejected
+                    }
+                }
+                ProposalKind::Poll { .. } => match winner {
+                    Some(option) => ProposalOutcome::Selected { option },
+                    None => ProposalOutcome::Tied { options: top },
+                },
+                ProposalKind::FarmingReward { .. } => ProposalOutcome::Allocated,
+            },
+            _ => ProposalOutcome::Pending,
+        };
+
+        ProposalResult {
+            proposal_id: self.id,
+            status: self.status.clone(),
+            options,
+            ranking,
+            winner,
+            total_ballots,
+            ve_amount_at_last_action: self.ve_amount_at_last_action,
+            turnout_bps: bps(total_ballots, self.ve_amount_at_last_action),
+            participants: self.participants,
+            outcome,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::*;
+use std::convert::TryFrom;
+
+pub const ROUND_PLACEHOLDER: &str = "{round}";
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalSchedule {
+    pub id: u32,
+    /// The operator who registered this schedule,
+    /// used as proposer of every materialized proposal.
+    pub proposer: AccountId,
+    /// Kind of every materialized proposal.
+    pub kind: ProposalKind,
+    /// Description template, `{round}` would be replaced with the round number.
+    pub description: String,
+    /// the seconds of voting begin time of the next round.
+    pub next_start_at: u32,
+    /// The voting duration of every round in seconds.
+    pub duration_sec: DurationSec,
+    /// The seconds between the voting begin time of two adjacent rounds.
+    pub interval_sec: DurationSec,
+    /// How many seconds ahead of next_start_at the next round can be materialized.
+    pub lead_sec: DurationSec,
+    /// The number of materialized rounds.
+    pub rounds: u32,
+    pub last_proposal_id: Option<u32>,
+    /// NEAR prepaid by the proposer for the storage of the rounds,
+    /// a round is skipped when it can't cover the storage.
+    #[serde(with = "u128_dec_format")]
+    pub storage_balance: Balance,
+}
+
+impl ProposalSchedule {
+    /// Returns the voting begin time of the round that can be materialized now.
+    /// Rounds whose begin time is too close to pass the create_proposal check are skipped.
+    /// lead_sec is clamped to min_start_vote_offset_sec, in case the offset was raised after registration.
+    pub fn due_start_at(&self, now: u32, min_start_vote_offset_sec: u32) -> Option<u64> {
+        let earliest = now as u64 + min_start_vote_offset_sec as u64;
+        let mut start_at = self.next_start_at as u64;
+        if start_at < earliest {
+            let missed_rounds = (earliest - start_at - 1) / self.interval_sec as u64 + 1;
+            start_at += missed_rounds * self.interval_sec as u64;
+        }
+        let lead_sec = std::cmp::max(self.lead_sec, min_start_vote_offset_sec);
+        if now as u64 + lead_sec as u64 >= start_at {
+            Some(start_at)
+        } else {
+            None
+        }
+    }
+
+    pub fn round_description(&self, round: u32) -> String {
+        self.description.replace(ROUND_PLACEHOLDER, &round.to_string())
+    }
+}
+
+impl Contract {
+    pub fn internal_get_proposal_schedule(&self, schedule_id: u32) -> Option<ProposalSchedule> {
+        self.data().proposal_schedules.get(&schedule_id)
+    }
+
+    pub fn internal_set_proposal_schedule(&mut self, schedule_id: u32, schedule: &ProposalSchedule) {
+        self.data_mut().proposal_schedules.insert(&schedule_id, schedule);
+    }
+
+    /// Returns the error a schedule would hit in create_proposal under current state,
+    /// so one broken schedule can not block the others.
+    pub fn internal_check_proposal_schedule(&self, schedule: &ProposalSchedule) -> Option<&'static str> {
+        let config = self.internal_config();
+        if !self.data().whitelisted_accounts.contains(&schedule.proposer) {
+            Some(E002_NOT_ALLOWED)
+        } else if self.internal_get_account(&schedule.proposer).is_none() {
+            Some(E100_ACC_NOT_REGISTERED)
+        } else if schedule.duration_sec < config.min_voting_duration_sec || schedule.duration_sec > config.max_voting_duration_sec {
+            Some(E302_INVALID_DURATION)
+        } else {
+            None
+        }
+    }
+
+    /// Materializes the due rounds of at most limit schedules from from_index.
+    pub fn internal_materialize_scheduled_proposals(&mut self, from_index: u64, limit: u64) -> Vec<u32> {
+        let now = nano_to_sec(env::block_timestamp());
+        let min_start_vote_offset_sec = self.internal_config().min_proposal_start_vote_offset_sec;
+        let keys = self.data().proposal_schedules.keys_as_vector();
+        let schedule_ids: Vec<u32> = (from_index..std::cmp::min(from_index.saturating_add(limit), keys.len()))
+            .map(|index| keys.get(index).unwrap())
+            .collect();
+        let mut proposal_ids = vec![];
+        for schedule_id in schedule_ids {
+            let mut schedule = self.internal_get_proposal_schedule(schedule_id).unwrap();
+            if let Some(start_at) = schedule.due_start_at(now, min_start_vote_offset_sec) {
+                let next_start_at = u32::try_from(start_at).ok()
+                    .and_then(|start_at| start_at.checked_add(schedule.interval_sec).map(|next| (start_at, next)));
+                let reason = if next_start_at.is_none() {
+                    Some(E417_SCHEDULE_OVERFLOW)
+                } else {
+                    self.internal_check_proposal_schedule(&schedule)
+                };
+                if let Some(reason) = reason {
+                    Event::ProposalScheduleSkip {
+                        schedule_id: schedule.id,
+                        reason,
+                    }
+                    .emit();
+                    continue;
+                }
+                let (start_at, next_start_at) = next_start_at.unwrap();
+                let round = schedule.rounds + 1;
+                let prev_storage = env::storage_usage();
+                let proposal_id = self.internal_create_proposal(
+                    &schedule.proposer,
+                    schedule.kind.clone(),
+                    schedule.round_description(round),
+                    None,
+                    vec![],
+                    start_at,
+                    schedule.duration_sec,
+                );
+                let storage_deposit = (env::storage_usage() - prev_storage) as Balance * env::storage_byte_cost();
+                if storage_deposit > schedule.storage_balance {
+                    self.internal_revert_last_proposal(proposal_id);
+                    Event::ProposalScheduleSkip {
+                        schedule_id: schedule.id,
+                        reason: E102_INSUFFICIENT_STORAGE,
+                    }
+                    .emit();
+                    continue;
+                }
+                let mut proposal = self.internal_unwrap_proposal(proposal_id);
+                proposal.storage_deposit = storage_deposit;
+                self.internal_set_proposal(proposal_id, proposal);
+                schedule.storage_balance -= storage_deposit;
+
+                Event::ProposalCreate {
+                    proposer_id: &schedule.proposer,
+                    proposal_id,
+                    kind: &format!("{:?}", schedule.kind),
+                    start_at: to_nano(start_at),
+                    duration_sec: schedule.duration_sec,
+                }
+                .emit();
+                schedule.rounds = round;
+                schedule.last_proposal_id = Some(proposal_id);
+                schedule.next_start_at = next_start_at;
+                self.internal_set_proposal_schedule(schedule.id, &schedule);
+
+                Event::ProposalScheduleRound {
+                    schedule_id: schedule.id,
+                    round,
+                    proposal_id,
+                }
+                .emit();
+                proposal_ids.push(proposal_id);
+            }
+        }
+        proposal_ids
+    }
+}
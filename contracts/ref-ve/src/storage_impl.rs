@@ -6,7 +6,6 @@ use near_contract_standards::storage_management::{
 /// Implements users storage management for the pool.
 #[near_bindgen]
 impl StorageManagement for Contract {
-    #[allow(unused_variables)]
     #[payable]
     fn storage_deposit(
         &mut self,
@@ -17,20 +16,25 @@ impl StorageManagement for Contract {
         let amount = env::attached_deposit();
         let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
         let sponsor_id = env::predecessor_account_id();
-        let already_registered = self.data().accounts.contains_key(&account_id);
-        if amount < STORAGE_BALANCE_MIN_BOUND && !already_registered {
-            env::panic_str(E102_INSUFFICIENT_STORAGE);
-        }
-
-        if already_registered {
-            if amount > 0 {
-                Promise::new(env::predecessor_account_id()).transfer(amount);
+        let registration_only = registration_only.unwrap_or(false);
+        if let Some(mut account) = self.internal_get_account(&account_id) {
+            if registration_only {
+                if amount > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(amount);
+                }
+            } else {
+                account.storage_balance += amount;
+                self.internal_set_account(&account_id, account);
             }
-        } else {     
-            self.ft.internal_register_account(&account_id);       
-            self.internal_set_account(&account_id, Account::new(&account_id, &sponsor_id));
+        } else {
+            require!(amount >= STORAGE_BALANCE_MIN_BOUND, E102_INSUFFICIENT_STORAGE);
+            self.ft.internal_register_account(&account_id);
+            let mut account = Account::new(&account_id, &sponsor_id);
+            account.storage_balance = if registration_only { STORAGE_BALANCE_MIN_BOUND } else { amount };
+            let refund = amount - account.storage_balance;
+            self.internal_set_account(&account_id, account);
             self.data_mut().account_count += 1;
-            let refund = amount - STORAGE_BALANCE_MIN_BOUND;
+            self.data_mut().account_index.insert(&account_id);
             if refund > 0 {
                 Promise::new(env::predecessor_account_id()).transfer(refund);
             }
@@ -38,11 +42,21 @@ impl StorageManagement for Contract {
         self.storage_balance_of(account_id).unwrap()
     }
 
-    #[allow(unused_variables)]
+    /// Withdraws the storage balance beyond what the account storage needs.
     #[payable]
     fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
         assert_one_yocto();
-        env::panic_str(E101_INSUFFICIENT_BALANCE);
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        let available = account.storage_available();
+        let amount = amount.map(|v| v.0).unwrap_or(available);
+        require!(amount <= available, E101_INSUFFICIENT_BALANCE);
+        if amount > 0 {
+            account.storage_balance -= amount;
+            self.internal_set_account(&account_id, account);
+            Promise::new(account_id.clone()).transfer(amount);
+        }
+        self.storage_balance_of(account_id).unwrap()
     }
 
     #[allow(unused_variables)]
@@ -57,7 +71,7 @@ impl StorageManagement for Contract {
         if let Some(account) = self.internal_get_account(&account_id) {
             
             require!(
-                account.rewards.is_empty(),
+                account.rewards.is_empty() && account.vesting_rewards.is_empty(),
                 E103_STILL_HAS_REWARD
             );
             require!(
@@ -70,10 +84,22 @@ impl StorageManagement for Contract {
                 E106_STILL_HAS_LOVE_TOKEN
             );
 
+            // the history left in state would be paid by nobody
+            require!(
+                account.proposals_history.is_empty(),
+                E108_STILL_HAS_VOTE_HISTORY
+            );
+
             self.internal_remove_account(&account_id);
+            // the fixed storage fee goes back to the sponsor, the rest of storage balance to the account
+            let mut refund = account.storage_balance;
             if account.sponsor_id != env::current_account_id(){
+                refund = refund.saturating_sub(STORAGE_BALANCE_MIN_BOUND);
                 Promise::new(account.sponsor_id).transfer(STORAGE_BALANCE_MIN_BOUND);
             }
+            if refund > 0 {
+                Promise::new(account_id).transfer(refund);
+            }
             true
         } else {
             false
@@ -83,18 +109,14 @@ impl StorageManagement for Contract {
     fn storage_balance_bounds(&self) -> StorageBalanceBounds {
         StorageBalanceBounds {
             min: U128(STORAGE_BALANCE_MIN_BOUND),
-            max: Some(U128(STORAGE_BALANCE_MIN_BOUND)),
+            max: None,
         }
     }
 
     fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
-        if self.data().accounts.contains_key(&account_id) {
-            Some(StorageBalance {
-                total: U128(STORAGE_BALANCE_MIN_BOUND),
-                available: U128(0),
-            })
-        }else{
-            None
-        }
+        self.internal_get_account(&account_id).map(|account| StorageBalance {
+            total: U128(account.storage_balance),
+            available: U128(account.storage_available()),
+        })
     }
 }
\ No newline at end of file
@@ -6,7 +6,7 @@ use near_sdk::{serde_json, PromiseOrValue};
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 enum FTokenReceiverMessage {
-    Reward { proposal_id: u32, incentive_key: u32 }
+    Reward { proposal_id: u32, incentive_key: u32, vesting_duration_sec: Option<u32> }
 }
 
 #[near_bindgen]
@@ -18,24 +18,12 @@ impl FungibleTokenReceiver for Contract {
         msg: String,
     ) -> PromiseOrValue<U128> {
         let amount: u128 = amount.into();
-        let token_id = env::predecessor_account_id();
+        let token_id = env::predecessor_account_id().to_string();
         let message =
             serde_json::from_str::<FTokenReceiverMessage>(&msg).expect(E500_INVALID_MSG);
         match message {
-            FTokenReceiverMessage::Reward { proposal_id, incentive_key } => {
-
-                let (total_amount, start_at) = self.internal_deposit_reward(proposal_id, incentive_key, &token_id, amount);
-
-                Event::RewardDeposit {
-                    caller_id: &sender_id,
-                    proposal_id,
-                    incentive_key,
-                    token_id: &token_id,
-                    deposit_amount: &U128(amount),
-                    total_amount: &U128(total_amount),
-                    start_at,
-                }
-                .emit();
+            FTokenReceiverMessage::Reward { proposal_id, incentive_key, vesting_duration_sec } => {
+                self.deposit_reward(&sender_id, proposal_id, incentive_key, &token_id, amount, vesting_duration_sec.unwrap_or(0));
             }
         }
         PromiseOrValue::Value(U128(0))
@@ -57,7 +45,8 @@ pub trait MFTTokenReceiver {
 #[serde(crate = "near_sdk::serde")]
 enum MFTokenReceiverMessage {
     Lock { duration_sec: u32 },
-    Append { append_duration_sec: u32 }
+    Append { append_duration_sec: u32 },
+    Reward { proposal_id: u32, incentive_key: u32, vesting_duration_sec: Option<u32> }
 }
 
 #[near_bindgen]
@@ -70,14 +59,20 @@ impl MFTTokenReceiver for Contract {
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128> {
+        let message =
+            serde_json::from_str::<MFTokenReceiverMessage>(&msg).expect(E500_INVALID_MSG);
+        if let MFTokenReceiverMessage::Reward { proposal_id, incentive_key, vesting_duration_sec } = message {
+            let token_id = mft_reward_token_id(&env::predecessor_account_id(), &token_id);
+            self.deposit_reward(&sender_id, proposal_id, incentive_key, &token_id, amount.into(), vesting_duration_sec.unwrap_or(0));
+            return PromiseOrValue::Value(U128(0));
+        }
+
         let (amount, refund) = self.real_amount_and_refund(amount.into());
         require!(amount > 0, E101_INSUFFICIENT_BALANCE);
 
         require!(token_id == self.data().lptoken_id, E600_MFT_INVALID_LPTOKEN_ID);
         require!(env::predecessor_account_id() == self.data().lptoken_contract_id, E601_MFT_INVALID_LPTOKEN_CONTRACT);
         
-        let message =
-            serde_json::from_str::<MFTokenReceiverMessage>(&msg).expect(E500_INVALID_MSG);
         match message {
             MFTokenReceiverMessage::Lock { duration_sec } => {
                 self.lock_lpt(&sender_id, amount, duration_sec);
@@ -85,6 +80,7 @@ impl MFTTokenReceiver for Contract {
             MFTokenReceiverMessage::Append { append_duration_sec } => {
                 self.append_lpt(&sender_id, amount, append_duration_sec);
             },
+            MFTokenReceiverMessage::Reward { .. } => unreachable!(),
         }
         PromiseOrValue::Value(U128(refund))
     }
@@ -92,20 +88,38 @@ impl MFTTokenReceiver for Contract {
 
 impl Contract {
 
+    pub fn deposit_reward(
+        &mut self,
+        sender_id: &AccountId,
+        proposal_id: u32,
+        incentive_key: u32,
+        token_id: &RewardTokenId,
+        amount: Balance,
+        vesting_duration_sec: u32,
+    ) {
+        let (total_amount, start_at) = self.internal_deposit_reward(proposal_id, incentive_key, token_id, amount, sender_id, vesting_duration_sec);
+
+        Event::RewardDeposit {
+            caller_id: sender_id,
+            proposal_id,
+            incentive_key,
+            token_id,
+            deposit_amount: &U128(amount),
+            total_amount: &U128(total_amount),
+            start_at,
+        }
+        .emit();
+    }
+
     pub fn lock_lpt(
         &mut self,
         account_id: &AccountId,
         amount: Balance,
         duration_sec: u32,
     ) {
+        let is_registered = self.internal_get_account(account_id).is_some();
         let mut account = self.internal_unwrap_or_default_account(account_id);
-        let config = self.internal_config();
-        require!(self.internal_get_account(account_id).is_some() ||  amount >= MIN_FIRST_LOCK, E503_FIRST_LOCK_TOO_FEW);
-        require!(duration_sec >= config.min_locking_duration_sec, E302_INVALID_DURATION);
-        require!(duration_sec <= config.max_locking_duration_sec, E302_INVALID_DURATION);
-
-        let increased_ve_lpt = account.lock_lpt(amount, duration_sec, &config, self.data().lptoken_decimals);
-        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
+        let increased_ve_lpt = self.internal_apply_lock(&mut account, is_registered, amount, duration_sec);
         self.mint_love_token(account_id, increased_ve_lpt);
 
         self.data_mut().cur_lock_lpt += amount;
@@ -131,16 +145,7 @@ impl Contract {
         append_duration_sec: u32,
     ) {
         let mut account = self.internal_unwrap_account(account_id);
-        require!(account.unlock_timestamp != 0, E105_ACC_NOT_LOCKED);
-        let timestamp = env::block_timestamp();
-        let duration_sec = nano_to_sec(account.unlock_timestamp) - nano_to_sec(timestamp) + append_duration_sec;
-
-        let config = self.internal_config();
-        require!(duration_sec >= config.min_locking_duration_sec, E302_INVALID_DURATION);
-        require!(duration_sec <= config.max_locking_duration_sec, E302_INVALID_DURATION);
-
-        let increased_ve_lpt = account.lock_lpt(amount, duration_sec, &config, self.data().lptoken_decimals);
-        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
+        let (increased_ve_lpt, duration_sec) = self.internal_apply_append(&mut account, amount, append_duration_sec);
         self.mint_love_token(account_id, increased_ve_lpt);
 
         self.data_mut().cur_lock_lpt += amount;
@@ -159,6 +164,34 @@ impl Contract {
         .emit();
     }
 
+    /// Locks amount into the account in memory, returns the increased ve lpt.
+    pub fn internal_apply_lock(&self, account: &mut Account, is_registered: bool, amount: Balance, duration_sec: u32) -> Balance {
+        let config = self.internal_config();
+        require!(is_registered || amount >= MIN_FIRST_LOCK, E503_FIRST_LOCK_TOO_FEW);
+        require!(duration_sec >= config.min_locking_duration_sec, E302_INVALID_DURATION);
+        require!(duration_sec <= config.max_locking_duration_sec, E302_INVALID_DURATION);
+
+        let increased_ve_lpt = account.lock_lpt(amount, duration_sec, &config, self.data().lptoken_decimals);
+        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
+        increased_ve_lpt
+    }
+
+    /// Appends amount to the lock of the account in memory,
+    /// returns the increased ve lpt and the new locking duration.
+    pub fn internal_apply_append(&self, account: &mut Account, amount: Balance, append_duration_sec: u32) -> (Balance, u32) {
+        require!(account.unlock_timestamp != 0, E105_ACC_NOT_LOCKED);
+        let timestamp = env::block_timestamp();
+        let duration_sec = nano_to_sec(account.unlock_timestamp) - nano_to_sec(timestamp) + append_duration_sec;
+
+        let config = self.internal_config();
+        require!(duration_sec >= config.min_locking_duration_sec, E302_INVALID_DURATION);
+        require!(duration_sec <= config.max_locking_duration_sec, E302_INVALID_DURATION);
+
+        let increased_ve_lpt = account.lock_lpt(amount, duration_sec, &config, self.data().lptoken_decimals);
+        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
+        (increased_ve_lpt, duration_sec)
+    }
+
     pub fn real_amount_and_refund(&self, amount: Balance) -> (Balance, Balance) {
         if self.data().lptoken_decimals > LOVE_DECIMAL {
             let decimals_diff = 10u128.pow((self.data().lptoken_decimals - LOVE_DECIMAL) as u32);
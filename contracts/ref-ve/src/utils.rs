@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
 // use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::{env, AccountId, Balance, Timestamp, Gas, ext_contract};
+use near_sdk::{env, AccountId, Balance, Timestamp, Gas, Promise, ext_contract};
 use near_sdk::json_types::U128;
+use near_contract_standards::fungible_token::core_impl::ext_fungible_token;
 
 use crate::errors::{E501_INVALID_FARM_INFO, E502_INVALID_TOKEN_ID};
 
@@ -11,6 +12,13 @@ uint::construct_uint!(
 );
 
 pub type DurationSec = u32;
+/// (proposal_id, incentive_key)
+pub type IncentiveId = (u32, u32);
+/// The account id of a NEP-141 token, "{contract_id}@{token_id}" of a MFT, or "NEAR".
+pub type RewardTokenId = String;
+
+pub const NEAR_REWARD_TOKEN_ID: &str = "NEAR";
+pub const MFT_TAG: char = '@';
 
 pub const LOVE_DECIMAL: u8 = 18;
 
@@ -22,6 +30,8 @@ pub const DEFAULT_MIN_LOCKING_DURATION_SEC: DurationSec = DAY_SEC * 30;
 pub const DEFAULT_MAX_LOCKING_DURATION_SEC: DurationSec = DAY_SEC * 30 * 12; 
 pub const DEFAULT_MAX_LOCKING_REWARD_RATIO: u32 = 20000;
 pub const MIN_LOCKING_REWARD_RATIO: u32 = 10000;
+pub const FEE_DIVISOR: u32 = 10000;
+pub const MAX_KEEPER_FEE_BPS: u32 = 1000;
 
 pub const TGAS: u64 = 1_000_000_000_000;
 pub const GAS_FOR_LPT_TRANSFER: Gas = Gas(20 * TGAS);
@@ -30,8 +40,34 @@ pub const GAS_FOR_REWARD_TRANSFER: Gas = Gas(20 * TGAS);
 pub const GAS_FOR_RESOLVE_REWARD_TRANSFER: Gas = Gas(10 * TGAS);
 pub const GAS_FOR_REMOVED_PROPOSAL_ASSETS: Gas = Gas(20 * TGAS);
 pub const GAS_FOR_RESOLVE_REMOVED_PROPOSAL_ASSETS: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_REWARD_LOSTFOUND: Gas = Gas(20 * TGAS);
+pub const GAS_FOR_RESOLVE_REWARD_LOSTFOUND: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_INCENTIVE_REFUND: Gas = Gas(20 * TGAS);
+pub const GAS_FOR_RESOLVE_INCENTIVE_REFUND: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_INCENTIVE_REFUND_WITHDRAW: Gas = Gas(20 * TGAS);
+pub const GAS_FOR_RESOLVE_INCENTIVE_REFUND_WITHDRAW: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_INCENTIVE_REFUND_FINISH: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_STORAGE_BALANCE_OF: Gas = Gas(5 * TGAS);
+pub const GAS_FOR_STORAGE_BALANCE_BOUNDS: Gas = Gas(5 * TGAS);
+pub const GAS_FOR_REWARD_STORAGE_DEPOSIT: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_RESOLVE_REWARD_STORAGE_DEPOSIT: Gas = Gas(5 * TGAS);
+pub const GAS_FOR_RESOLVE_REWARD_STORAGE: Gas = Gas(55 * TGAS);
+pub const GAS_FOR_WITHDRAW_REWARDS_FINISH: Gas = Gas(10 * TGAS);
 
 pub const DESCRIPTION_LIMIT: usize = 2048;
+pub const METADATA_TITLE_LIMIT: usize = 256;
+pub const METADATA_URI_LIMIT: usize = 512;
+pub const TAG_LIMIT: usize = 32;
+pub const MAX_PROPOSAL_TAGS: usize = 5;
+pub const PROPOSAL_SCAN_LIMIT: usize = 1000;
+/// Default number of schedules visited by one materialize_scheduled_proposals call.
+pub const DEFAULT_SCHEDULE_BATCH_LIMIT: u64 = 20;
+/// Max accounts indexed by one index_accounts call.
+pub const ACCOUNT_INDEX_BATCH_LIMIT: usize = 100;
+/// Max proposals visited by one index_proposals call.
+pub const PROPOSAL_INDEX_BATCH_LIMIT: u32 = 100;
+/// Default number of history entries visited by one prune_proposal_history call.
+pub const DEFAULT_HISTORY_BATCH_LIMIT: u64 = 100;
 pub const MIN_FIRST_LOCK: u128 = 10u128.pow(22);
 pub const STORAGE_BALANCE_MIN_BOUND: u128 = 1_250_000_000_000_000_000_000;
 
@@ -111,11 +147,11 @@ pub mod u128_map_format {
     use near_sdk::serde::de;
     use near_sdk::serde::ser::Serialize;
     use near_sdk::serde::{Deserialize, Deserializer, Serializer};
-    use near_sdk::{AccountId, Balance};
+    use near_sdk::Balance;
     use std::collections::HashMap;
 
 
-    pub fn serialize<S>(info: &HashMap<AccountId, Balance>, serializer: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<S>(info: &HashMap<String, Balance>, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -126,11 +162,11 @@ pub mod u128_map_format {
         Vec::serialize(&res, serializer)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<AccountId, Balance>, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<String, Balance>, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let str_vec: Vec<(AccountId, String)> = Vec::deserialize(deserializer)?;
+        let str_vec: Vec<(String, String)> = Vec::deserialize(deserializer)?;
         let mut res = HashMap::new();
         for (account_id, balance_str) in str_vec.into_iter() {
             let balance: u128 = balance_str.parse().map_err(de::Error::custom)?;
@@ -157,6 +193,50 @@ pub fn extra_incentive_tokens(farm_info: String) -> HashSet<AccountId> {
     farm_tokens_str.split('|').into_iter().map(|a| a.parse().unwrap_or_else(|_| env::panic_str(E502_INVALID_TOKEN_ID))).collect()
 }
 
+pub fn mft_reward_token_id(contract_id: &AccountId, token_id: &str) -> RewardTokenId {
+    format!("{}{}{}", contract_id, MFT_TAG, token_id)
+}
+
+pub fn is_valid_reward_token_id(token_id: &str) -> bool {
+    if token_id == NEAR_REWARD_TOKEN_ID {
+        true
+    } else if let Some((contract_id, inner_id)) = token_id.split_once(MFT_TAG) {
+        contract_id.parse::<AccountId>().is_ok() && !inner_id.is_empty()
+    } else {
+        token_id.parse::<AccountId>().is_ok()
+    }
+}
+
+pub fn is_nep141_reward_token_id(token_id: &str) -> bool {
+    token_id != NEAR_REWARD_TOKEN_ID && !token_id.contains(MFT_TAG)
+}
+
+/// Transfers reward token by ft_transfer, mft_transfer or a NEAR transfer according to its id.
+pub fn reward_token_transfer(token_id: &str, receiver_id: &AccountId, amount: Balance, gas: Gas) -> Promise {
+    if token_id == NEAR_REWARD_TOKEN_ID {
+        Promise::new(receiver_id.clone()).transfer(amount)
+    } else if let Some((contract_id, inner_id)) = token_id.split_once(MFT_TAG) {
+        ext_multi_fungible_token::mft_transfer(
+            inner_id.to_string(),
+            receiver_id.clone(),
+            amount.into(),
+            None,
+            contract_id.parse().unwrap(),
+            1,
+            gas,
+        )
+    } else {
+        ext_fungible_token::ft_transfer(
+            receiver_id.clone(),
+            amount.into(),
+            None,
+            token_id.parse().unwrap(),
+            1,
+            gas,
+        )
+    }
+}
+
 #[ext_contract(ext_multi_fungible_token)]
 pub trait MultiFungibleToken {
     fn mft_transfer(
@@ -168,20 +248,51 @@ pub trait MultiFungibleToken {
     );
 }
 
+#[ext_contract(ext_reward_token_storage)]
+pub trait RewardTokenStorage {
+    fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>);
+
+    fn storage_balance_of(&self, account_id: AccountId);
+
+    fn storage_balance_bounds(&self);
+}
+
 #[ext_contract(ext_self)]
 pub trait TokenPostActions {
     fn callback_post_withdraw_reward(
-        &mut self, token_id: AccountId, sender_id: AccountId, amount: U128,
+        &mut self, token_id: RewardTokenId, sender_id: AccountId, amount: U128,
+    );
+
+    fn callback_reward_storage_balance_of(
+        &mut self, token_id: RewardTokenId, sender_id: AccountId, receiver_id: AccountId, amount: U128,
     );
 
+    fn callback_reward_storage_deposit(&mut self, deposit: U128);
+
     fn callback_removed_proposal_assets(
-        &mut self, token_id: AccountId, receiver_id: AccountId, amount: U128,
+        &mut self, token_id: RewardTokenId, receiver_id: AccountId, amount: U128,
+    );
+
+    fn callback_refund_unused_incentive(
+        &mut self, incentive_id: IncentiveId, token_id: RewardTokenId, receiver_id: AccountId, amount: U128,
+    );
+
+    fn callback_sweep_incentive_dust(
+        &mut self, incentive_id: IncentiveId, token_id: RewardTokenId, treasury_id: AccountId, amount: U128,
     );
 
     fn callback_withdraw_lpt(&mut self, sender_id: AccountId, amount: U128);
 
     fn callback_withdraw_lpt_lostfound(&mut self, receiver_id: AccountId, amount: U128);
 
+    fn callback_withdraw_incentive_refund(
+        &mut self, token_id: RewardTokenId, receiver_id: AccountId, amount: U128,
+    );
+
+    fn callback_withdraw_reward_lostfound(
+        &mut self, token_id: RewardTokenId, receiver_id: AccountId, amount: U128,
+    );
+
     fn callback_withdraw_lpt_slashed(&mut self, sender_id: AccountId, amount: U128);
 }
 
@@ -8,22 +8,39 @@ pub struct Metadata {
     pub version: String,
     pub owner_id: AccountId,
     pub operators: Vec<AccountId>,
+    pub guardians: Vec<AccountId>,
     pub whitelisted_accounts: Vec<AccountId>,
-    pub whitelisted_incentive_tokens: Vec<AccountId>,
+    pub whitelisted_incentive_tokens: Vec<RewardTokenId>,
     pub lptoken_contract_id: AccountId,
     pub lptoken_id: String,
     pub lptoken_decimals: u8,
     pub account_count: U64,
+    /// Accounts in the account index, equals account_count once index_accounts has covered all.
+    pub indexed_account_count: U64,
     pub proposal_count: U64,
     pub cur_total_ve_lpt: U128,
     pub cur_lock_lpt: U128,
     pub lostfound: U128,
+    pub treasury_id: Option<AccountId>,
+    pub reward_storage_budget: U128,
+    pub keeper_fee_bps: u32,
+    pub archive_history: bool,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug, PartialEq))]
+pub struct IncentiveDeposit {
+    pub token_id: RewardTokenId,
+    pub depositor_id: AccountId,
+    pub amount: U128,
 }
 
 #[derive(Serialize)]
 #[serde(crate = "near_sdk::serde")]
 #[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
 pub struct AccountInfo {
+    pub account_id: AccountId,
     pub sponsor_id: AccountId,
     /// The amount of LPT locked
     #[serde(with = "u128_dec_format")]
@@ -37,7 +54,38 @@ pub struct AccountInfo {
     /// The duration of current locking in seconds.
     pub duration_sec: u32,
     #[serde(with = "u128_map_format")]
-    pub rewards: HashMap<AccountId, Balance>,
+    pub rewards: HashMap<RewardTokenId, Balance>,
+    /// Rewards still vesting, the vested part is counted in rewards
+    pub vesting_rewards: Vec<VestingReward>,
+    pub reward_receiver: Option<AccountId>,
+    pub keeper_claim_disabled: bool,
+}
+
+/// The full proposal, or the summary for an archived one or when asked.
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(untagged)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub enum ProposalInfo {
+    Full(Proposal),
+    Summary(ProposalSummary),
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug, PartialEq))]
+pub struct VoteHistory {
+    pub proposal_id: u32,
+    pub vote_detail: VoteDetail,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug, PartialEq))]
+pub struct RewardLostfound {
+    pub account_id: AccountId,
+    pub token_id: RewardTokenId,
+    pub amount: U128,
 }
 
 #[derive(Serialize)]
@@ -56,16 +104,22 @@ impl Contract {
             version: env!("CARGO_PKG_VERSION").to_string(),
             owner_id: self.data().owner_id.clone(),
             operators: self.data().operators.to_vec(),
+            guardians: self.data().guardians.to_vec(),
             whitelisted_accounts: self.data().whitelisted_accounts.to_vec(),
             whitelisted_incentive_tokens: self.data().whitelisted_incentive_tokens.iter().cloned().collect(),
             lptoken_contract_id: self.data().lptoken_contract_id.clone(),
             lptoken_id: self.data().lptoken_id.clone(),
             lptoken_decimals: self.data().lptoken_decimals,
             account_count: self.data().account_count.into(),
+            indexed_account_count: self.data().account_index.len().into(),
             proposal_count: self.data().proposals.len().into(),
             cur_total_ve_lpt: self.data().cur_total_ve_lpt.into(),
             cur_lock_lpt: self.data().cur_lock_lpt.into(),
             lostfound: self.data().lostfound.into(),
+            treasury_id: self.data().treasury_id.clone(),
+            reward_storage_budget: self.data().reward_storage_budget.into(),
+            keeper_fee_bps: self.data().keeper_fee_bps,
+            archive_history: self.data().archive_history,
         }
     }
 
@@ -81,6 +135,7 @@ impl Contract {
         }
     }
 
+    /// Archived proposals are skipped, so a page may have less than limit proposals.
     pub fn list_proposals(
         &self,
         from_index: Option<u64>,
@@ -90,27 +145,107 @@ impl Contract {
         let from_index = from_index.unwrap_or(0);
         let limit = limit.unwrap_or(values.len());
         (from_index..std::cmp::min(values.len(), from_index + limit))
-            .map(|index| {
-                let mut proposal: Proposal = values.get(index).unwrap().into();
+            .filter_map(|index| {
+                let mut proposal = values.get(index).unwrap().into_proposal()?;
                 proposal.update_status();
-                proposal
+                Some(proposal)
             })
             .collect()
     }
 
-    pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
-        if let Some(mut proposal) = self.internal_get_proposal(proposal_id) {
-            proposal.update_status();
-            Some(proposal)
+    /// Proposals matching the filter, newest first. Removed and archived proposals are skipped,
+    /// and at most PROPOSAL_SCAN_LIMIT proposals are checked in one call,
+    /// so a page may have less than limit proposals while next_cursor is not None.
+    pub fn list_proposals_by_filter(
+        &self,
+        filter: Option<ProposalFilter>,
+        from_proposal_id: Option<u32>,
+        limit: Option<u32>,
+    ) -> ProposalPage {
+        self.internal_list_proposals_by_filter(&filter.unwrap_or_default(), from_proposal_id, limit.unwrap_or(100))
+    }
+
+    /// An archived proposal always returns its summary.
+    pub fn get_proposal(&self, proposal_id: u32, summary_only: Option<bool>) -> Option<ProposalInfo> {
+        match self.data().proposals.get(&proposal_id)? {
+            VProposal::Archived(summary) => Some(ProposalInfo::Summary(summary)),
+            proposal => {
+                let mut proposal = proposal.into_proposal().unwrap();
+                proposal.update_status();
+                if summary_only.unwrap_or(false) {
+                    Some(ProposalInfo::Summary(proposal.to_summary()))
+                } else {
+                    Some(ProposalInfo::Full(proposal))
+                }
+            }
+        }
+    }
+
+    /// Ballots, shares and the outcome computed from the votes,
+    /// None for an archived proposal, whose summary keeps the final votes.
+    pub fn get_proposal_result(&self, proposal_id: u32) -> Option<ProposalResult> {
+        let mut proposal = self.internal_get_proposal(proposal_id)?;
+        proposal.update_status();
+        Some(proposal.to_result())
+    }
+
+    /// Metadata is kept after the proposal is archived.
+    pub fn get_proposal_metadata(&self, proposal_id: u32) -> Option<ProposalMetadata> {
+        match self.data().proposals.get(&proposal_id)? {
+            VProposal::Archived(summary) => summary.metadata,
+            proposal => proposal.into_proposal().unwrap().metadata,
+        }
+    }
+
+    /// Deposits of an incentive not yet refunded.
+    pub fn list_incentive_deposits(
+        &self,
+        proposal_id: u32,
+        incentive_key: u32,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<IncentiveDeposit> {
+        if let Some(deposits) = self.internal_get_incentive_deposits((proposal_id, incentive_key)) {
+            let keys = deposits.keys_as_vector();
+            let values = deposits.values_as_vector();
+            let from_index = from_index.unwrap_or(0);
+            let limit = limit.unwrap_or(keys.len());
+            (from_index..std::cmp::min(keys.len(), from_index + limit))
+                .map(|index| {
+                    let (token_id, depositor_id) = keys.get(index).unwrap();
+                    IncentiveDeposit {
+                        token_id,
+                        depositor_id,
+                        amount: values.get(index).unwrap().into(),
+                    }
+                })
+                .collect()
         } else {
-            None
+            vec![]
         }
     }
 
+    pub fn list_proposal_schedules(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<ProposalSchedule> {
+        let values = self.data().proposal_schedules.values_as_vector();
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(values.len());
+        (from_index..std::cmp::min(values.len(), from_index + limit))
+            .map(|index| values.get(index).unwrap())
+            .collect()
+    }
+
+    pub fn get_proposal_schedule(&self, schedule_id: u32) -> Option<ProposalSchedule> {
+        self.internal_get_proposal_schedule(schedule_id)
+    }
+
     pub fn get_unclaimed_rewards(
         &self,
         account_id: AccountId,
-    ) -> HashMap<AccountId, U128> {
+    ) -> HashMap<RewardTokenId, U128> {
         let rewards = self.internal_calc_account_unclaim_rewards(&account_id);
         rewards
             .into_iter()
@@ -122,18 +257,8 @@ impl Contract {
         &self,
         account_id: AccountId
     ) -> Option<AccountInfo> {
-        if let Some(account) = self.internal_get_account(&account_id) {
-            Some(AccountInfo {
-                sponsor_id: account.sponsor_id,
-                lpt_amount: account.lpt_amount,
-                ve_lpt_amount: account.ve_lpt_amount,
-                unlock_timestamp: account.unlock_timestamp,
-                duration_sec: account.duration_sec,
-                rewards: account.rewards,
-            })
-        } else {
-            None
-        }
+        self.internal_get_account(&account_id)
+            .map(|account| account.to_info(account_id))
     }
 
     pub fn get_vote_detail(
@@ -144,7 +269,7 @@ impl Contract {
             let mut result = HashMap::new();
             for (proposal_id, vote_detail) in account.proposals {
                 let proposal = self.internal_unwrap_proposal(proposal_id);
-                if proposal.status != Some(ProposalStatus::Expired) {
+                if proposal.status != Some(ProposalStatus::Expired) && proposal.status != Some(ProposalStatus::Cancelled) {
                     result.insert(proposal_id, vote_detail.clone());
                 }
             }
@@ -154,6 +279,33 @@ impl Contract {
         }
     }
 
+    /// Vote history of the account in storage order, which is not ordered by proposal id.
+    pub fn list_vote_detail_history(
+        &self,
+        account_id: AccountId,
+        from_index: Option<u64>,
+        limit: Option<u64>
+    ) -> Vec<VoteHistory> {
+        if let Some(account) = self.internal_get_account(&account_id) {
+            let keys = account.proposals_history.keys_as_vector();
+            let values = account.proposals_history.values_as_vector();
+            let from_index = from_index.unwrap_or(0);
+            let limit = limit.unwrap_or(keys.len());
+            (from_index..std::cmp::min(keys.len(), from_index.saturating_add(limit)))
+                .map(|index| VoteHistory {
+                    proposal_id: keys.get(index).unwrap(),
+                    vote_detail: values.get(index).unwrap(),
+                })
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    pub fn get_history_stats(&self, account_id: AccountId) -> Option<HistoryStats> {
+        self.internal_get_account(&account_id).map(|account| account.history_stats)
+    }
+
     pub fn get_vote_detail_history(
         &self,
         account_id: AccountId
@@ -195,7 +347,7 @@ impl Contract {
         }
     }
 
-    pub fn list_removed_proposal_assets(&self, from_index: Option<u64>, limit: Option<u64>) -> HashMap<AccountId, U128> {
+    pub fn list_removed_proposal_assets(&self, from_index: Option<u64>, limit: Option<u64>) -> HashMap<RewardTokenId, U128> {
         let keys = self.data().removed_proposal_assets.keys_as_vector();
 
         let from_index = from_index.unwrap_or(0);
@@ -210,4 +362,66 @@ impl Contract {
             })
             .collect()
     }
-}
\ No newline at end of file
+
+    /// Incentives of removed proposals the account could withdraw.
+    pub fn get_incentive_refunds(&self, account_id: AccountId) -> HashMap<RewardTokenId, U128> {
+        self.data().incentive_refunds.get(&account_id).unwrap_or_default()
+            .into_iter()
+            .map(|(token_id, amount)| (token_id, amount.into()))
+            .collect()
+    }
+
+    pub fn get_lpt_lostfound(&self, account_id: AccountId) -> U128 {
+        self.data().lpt_lostfound.get(&account_id).unwrap_or(0).into()
+    }
+
+    pub fn list_lpt_lostfound(&self, from_index: Option<u64>, limit: Option<u64>) -> HashMap<AccountId, U128> {
+        let keys = self.data().lpt_lostfound.keys_as_vector();
+
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(keys.len());
+
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| {
+                (
+                    keys.get(index).unwrap(),
+                    self.data().lpt_lostfound.get(&keys.get(index).unwrap()).unwrap().into()
+                )
+            })
+            .collect()
+    }
+
+    pub fn list_reward_lostfound(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<RewardLostfound> {
+        let keys = self.data().reward_lostfound.keys_as_vector();
+
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(keys.len());
+
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| {
+                let (account_id, token_id) = keys.get(index).unwrap();
+                let amount = self.data().reward_lostfound.get(&(account_id.clone(), token_id.clone())).unwrap();
+                RewardLostfound {
+                    account_id,
+                    token_id,
+                    amount: amount.into(),
+                }
+            })
+            .collect()
+    }
+
+    /// Accounts in the account index, in index order.
+    pub fn list_accounts(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<AccountInfo> {
+        let keys = self.data().account_index.as_vector();
+
+        let from_index = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(keys.len());
+
+        (from_index..std::cmp::min(from_index + limit, keys.len()))
+            .map(|index| {
+                let account_id = keys.get(index).unwrap();
+                self.internal_unwrap_account(&account_id).to_info(account_id)
+            })
+            .collect()
+    }
+}
@@ -16,4 +16,35 @@ impl Env {
                 1,
             )
     }
+
+    pub fn retry_withdraw_lpt(
+        &self,
+        operator: &UserAccount,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.retry_withdraw_lpt(),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn prune_proposal_history(
+        &self,
+        operator: &UserAccount,
+        before_proposal_id: u32,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.prune_proposal_history(
+                    before_proposal_id, from_index, limit
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
 }
\ No newline at end of file
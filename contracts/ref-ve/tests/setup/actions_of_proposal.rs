@@ -14,7 +14,82 @@ impl Env {
         operator
             .function_call(
                 self.ve_contract.contract.create_proposal(
-                    kind, description, start_at, duration_sec
+                    kind, description, start_at, duration_sec, None, None
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn create_proposal_with_metadata(
+        &self,
+        operator: &UserAccount,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        metadata: ProposalMetadata
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.create_proposal(
+                    kind, description, start_at, duration_sec, Some(metadata), None
+                ),
+                MAX_GAS.0,
+                PROPOSAL_STORAGE_DEPOSIT,
+            )
+    }
+
+    pub fn create_proposal_with_tags(
+        &self,
+        operator: &UserAccount,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        tags: Vec<&str>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.create_proposal(
+                    kind, description, start_at, duration_sec, None, Some(tags.iter().map(|tag| tag.to_string()).collect())
+                ),
+                MAX_GAS.0,
+                PROPOSAL_STORAGE_DEPOSIT,
+            )
+    }
+
+    pub fn cancel_proposal(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32,
+        reason: String,
+        deposit: u128,
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.cancel_proposal(
+                    proposal_id, reason
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn update_proposal(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32,
+        description: Option<String>,
+        options: Option<Vec<String>>,
+        start_at: Option<u32>,
+        duration_sec: Option<u32>,
+        deposit: u128,
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.update_proposal(
+                    proposal_id, description, options, start_at, duration_sec
                 ),
                 MAX_GAS.0,
                 deposit,
@@ -36,6 +111,21 @@ impl Env {
             )
     }
 
+    pub fn archive_proposal(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.archive_proposal(
+                    proposal_id
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
     pub fn action_proposal(
         &self,
         operator: &UserAccount,
@@ -65,4 +155,25 @@ impl Env {
                 1,
             )
     }
+
+    pub fn materialize_scheduled_proposals(
+        &self,
+        operator: &UserAccount
+    ) -> ExecutionResult {
+        self.materialize_scheduled_proposals_in_range(operator, None, None)
+    }
+
+    pub fn materialize_scheduled_proposals_in_range(
+        &self,
+        operator: &UserAccount,
+        from_index: Option<u64>,
+        limit: Option<u64>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.materialize_scheduled_proposals(from_index, limit),
+                MAX_GAS.0,
+                0,
+            )
+    }
 }
\ No newline at end of file
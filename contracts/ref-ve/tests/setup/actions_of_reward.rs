@@ -29,6 +29,68 @@ impl Env {
             )
     }
 
+    pub fn keeper_claim(
+        &self,
+        operator: &UserAccount, 
+        accounts: Vec<&UserAccount>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.keeper_claim(
+                    accounts.iter().map(|v| v.account_id()).collect()
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn set_keeper_claim_disabled(
+        &self,
+        operator: &UserAccount, 
+        disabled: bool,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.set_keeper_claim_disabled(
+                    disabled
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn claim_rewards(
+        &self,
+        operator: &UserAccount, 
+        proposal_ids: Vec<u32>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.claim_rewards(
+                    proposal_ids
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn withdraw_rewards(
+        &self,
+        operator: &UserAccount, 
+        token_ids: Vec<&UserAccount>,
+        gas: u64
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_rewards(
+                    token_ids.iter().map(|v| v.account_id().to_string()).collect()
+                ),
+                gas,
+                0,
+            )
+    }
+
     pub fn withdraw_reward(
         &self,
         operator: &UserAccount, 
@@ -37,11 +99,125 @@ impl Env {
         operator
             .function_call(
                 self.ve_contract.contract.withdraw_reward(
-                    token_id.account_id(), 
-                    if let Some(amount) = amount { Some(U128(amount)) } else { None }
+                    token_id.account_id().to_string(), 
+                    if let Some(amount) = amount { Some(U128(amount)) } else { None },
+                    None
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn withdraw_reward_token(
+        &self,
+        operator: &UserAccount, 
+        token_id: &str, amount: Option<u128>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_reward(
+                    token_id.to_string(), 
+                    amount.map(U128),
+                    None
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn withdraw_reward_with_registration(
+        &self,
+        operator: &UserAccount, 
+        token_id: &UserAccount, amount: Option<u128>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_reward(
+                    token_id.account_id().to_string(), 
+                    amount.map(U128),
+                    Some(true)
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn withdraw_incentive_refund(
+        &self,
+        operator: &UserAccount, 
+        token_id: &UserAccount
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_incentive_refund(
+                    token_id.account_id().to_string()
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn set_reward_receiver(
+        &self,
+        operator: &UserAccount, 
+        receiver: Option<&UserAccount>,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.set_reward_receiver(
+                    receiver.map(|v| v.account_id())
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn deposit_near_incentive(
+        &self,
+        operator: &UserAccount, 
+        proposal_id: u32,
+        incentive_key: u32,
+        amount: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.deposit_near_incentive(
+                    proposal_id, incentive_key, None
+                ),
+                MAX_GAS.0,
+                amount,
+            )
+    }
+
+    pub fn refund_unused_incentive(
+        &self,
+        operator: &UserAccount, 
+        proposal_id: u32,
+        incentive_key: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.refund_unused_incentive(
+                    proposal_id, incentive_key
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn sweep_incentive_dust(
+        &self,
+        operator: &UserAccount, 
+        proposal_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.sweep_incentive_dust(
+                    proposal_id
                 ),
                 MAX_GAS.0,
                 0,
             )
     }
-}
\ No newline at end of file
+}
@@ -17,6 +17,37 @@ impl Env {
             )
     }
 
+    pub fn index_accounts(
+        &self,
+        operator: &UserAccount,
+        account_ids: Vec<AccountId>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.index_accounts(
+                    account_ids
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn index_proposals(
+        &self,
+        operator: &UserAccount,
+        from_proposal_id: u32,
+        limit: Option<u32>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.index_proposals(
+                    from_proposal_id, limit
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
     pub fn remove_whitelisted_accounts(
         &self,
         operator: &UserAccount,
@@ -32,30 +63,30 @@ impl Env {
             )
     }
 
-    pub fn extend_whitelisted_incentive_tokens(
+    pub fn extend_whitelisted_incentive_tokens<T: ToString>(
         &self,
         operator: &UserAccount,
-        tokens: Vec<AccountId>
+        tokens: Vec<T>
     ) -> ExecutionResult {
         operator
             .function_call(
                 self.ve_contract.contract.extend_whitelisted_incentive_tokens(
-                    tokens
+                    tokens.iter().map(|token| token.to_string()).collect()
                 ),
                 MAX_GAS.0,
                 1,
             )
     }
 
-    pub fn remove_whitelisted_incentive_tokens(
+    pub fn remove_whitelisted_incentive_tokens<T: ToString>(
         &self,
         operator: &UserAccount,
-        tokens: Vec<AccountId>
+        tokens: Vec<T>
     ) -> ExecutionResult {
         operator
             .function_call(
                 self.ve_contract.contract.remove_whitelisted_incentive_tokens(
-                    tokens
+                    tokens.iter().map(|token| token.to_string()).collect()
                 ),
                 MAX_GAS.0,
                 1,
@@ -92,6 +123,36 @@ impl Env {
             )
     }
 
+    pub fn modify_archive_history(
+        &self,
+        operator: &UserAccount,
+        archive_history: bool
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_archive_history(
+                    archive_history
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_keeper_fee_bps(
+        &self,
+        operator: &UserAccount,
+        keeper_fee_bps: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_keeper_fee_bps(
+                    keeper_fee_bps
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
     pub fn return_lpt_lostfound(
         &self,
         operator: &UserAccount,
@@ -115,7 +176,22 @@ impl Env {
         operator
             .function_call(
                 self.ve_contract.contract.return_removed_proposal_assets(
-                    account.account_id(), token.account_id(), amount.into()
+                    account.account_id(), token.account_id().to_string(), amount.into()
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn return_reward_lostfound(
+        &self,
+        operator: &UserAccount,
+        account: &UserAccount, token: &UserAccount, amount: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.return_reward_lostfound(
+                    account.account_id(), token.account_id().to_string(), amount.into()
                 ),
                 MAX_GAS.0,
                 1,
@@ -136,4 +212,56 @@ impl Env {
                 1,
             )
     }
+
+    pub fn register_proposal_schedule(
+        &self,
+        operator: &UserAccount,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        interval_sec: u32,
+        lead_sec: u32,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.register_proposal_schedule(
+                    kind, description, start_at, duration_sec, interval_sec, lead_sec
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn top_up_proposal_schedule(
+        &self,
+        operator: &UserAccount,
+        schedule_id: u32,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.top_up_proposal_schedule(
+                    schedule_id
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn remove_proposal_schedule(
+        &self,
+        operator: &UserAccount,
+        schedule_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.remove_proposal_schedule(
+                    schedule_id
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
 }
\ No newline at end of file
@@ -10,22 +10,24 @@ pub use near_sdk_sim::{
 use mock_mft::ContractContract as MockMultiFungibleToken;
 
 pub use ref_ve::{ContractContract as VeContract,
-    Metadata, Proposal, ProposalKind, Action, Account, Config, VoteDetail, AccountInfo, VoteInfo
+    Metadata, Proposal, ProposalKind, ProposalStatus, Cancellation, Action, Account, Config, VoteDetail, AccountInfo, VoteInfo, ProposalSchedule, IncentiveDeposit, VestingReward
 };
 
 pub use ref_ve::{
-    DAY_SEC,
+    DAY_SEC, STORAGE_BALANCE_MIN_BOUND,
     DEFAULT_MIN_PROPOSAL_START_VOTE_OFFSET_SEC, DEFAULT_MAX_LOCKING_DURATION_SEC, DEFAULT_MAX_LOCKING_REWARD_RATIO,
     DEFAULT_MIN_LOCKING_DURATION_SEC, DEFAULT_MIN_VOTING_DURATION_SEC, DEFAULT_MAX_VOTING_DURATION_SEC
 };
 
+pub use ref_ve::{ClaimRewardsResult, WithdrawRewardsResult, KeeperClaimResult, MAX_KEEPER_FEE_BPS, RewardLostfound, HistoryStats, VoteHistory, ProposalInfo, ProposalSummary, ProposalMetadata, ProposalFilter, ProposalPage, ProposalResult, ProposalOutcome, LockPreview, WithdrawPreview, VoteRewardPreview, ProjectedReward, ACCOUNT_INDEX_BATCH_LIMIT};
+
 pub use ref_ve::{
     E002_NOT_ALLOWED, 
-    E100_ACC_NOT_REGISTERED, E101_INSUFFICIENT_BALANCE, E102_INSUFFICIENT_STORAGE, E103_STILL_HAS_REWARD, E104_STILL_HAS_LPT,E106_STILL_HAS_LOVE_TOKEN,
-    E200_ALREADY_VOTED, E201_INVALID_VOTE, E203_INVALID_INCENTIVE_TOKEN, E204_VOTE_CAN_NOT_CANCEL, E205_NOT_VOTABLE, E206_NO_VOTED, E207_INVALID_INCENTIVE_KEY, E208_DESCRIPTION_TOO_LONG,
-    E301_INVALID_RATIO, E302_INVALID_DURATION, E305_STILL_IN_LOCK, E306_INVALID_LOCK_DURATION_LIMIT, E307_INVALID_VOTING_DURATION_LIMIT, E308_UNECONOMIC_LOCK,
-    E402_INVALID_START_TIME, E404_PROPOSAL_NOT_EXIST, E406_EXPIRED_PROPOSAL,
-    E503_FIRST_LOCK_TOO_FEW
+    E100_ACC_NOT_REGISTERED, E101_INSUFFICIENT_BALANCE, E102_INSUFFICIENT_STORAGE, E103_STILL_HAS_REWARD, E104_STILL_HAS_LPT,E106_STILL_HAS_LOVE_TOKEN, E107_TOO_MANY_ACCOUNTS, E108_STILL_HAS_VOTE_HISTORY,
+    E200_ALREADY_VOTED, E201_INVALID_VOTE, E203_INVALID_INCENTIVE_TOKEN, E204_VOTE_CAN_NOT_CANCEL, E205_NOT_VOTABLE, E206_NO_VOTED, E207_INVALID_INCENTIVE_KEY, E208_DESCRIPTION_TOO_LONG, E209_INCENTIVE_STILL_CLAIMABLE, E210_NOTHING_TO_REFUND, E211_PROPOSAL_SETTLED, E212_REWARD_NOT_ALL_CLAIMED, E213_UNUSED_INCENTIVE_NOT_REFUNDED, E214_INVALID_VESTING_DURATION, E215_METADATA_TOO_LONG, E216_INVALID_CONTENT_HASH,
+    E301_INVALID_RATIO, E302_INVALID_DURATION, E305_STILL_IN_LOCK, E306_INVALID_LOCK_DURATION_LIMIT, E307_INVALID_VOTING_DURATION_LIMIT, E308_UNECONOMIC_LOCK, E309_TREASURY_NOT_SET, E310_INSUFFICIENT_STORAGE_BUDGET,
+    E402_INVALID_START_TIME, E404_PROPOSAL_NOT_EXIST, E406_EXPIRED_PROPOSAL, E407_INVALID_SCHEDULE, E408_SCHEDULE_NOT_EXIST, E409_PROPOSAL_NOT_EXPIRED, E410_PROPOSAL_NOT_SETTLED, E411_PROPOSAL_ARCHIVED, E412_PROPOSAL_NOT_WARMUP, E413_INVALID_OPTIONS, E414_PROPOSAL_CANCELLED, E415_PROPOSAL_CLAIMED, E416_INVALID_TAGS, E417_SCHEDULE_OVERFLOW,
+    E502_INVALID_TOKEN_ID, E503_FIRST_LOCK_TOO_FEW
 };
 
 mod users;
@@ -54,6 +56,8 @@ pub use views::*;
 
 near_sdk_sim::lazy_static_include::lazy_static_include_bytes! {
     PREV_REF_VE_WASM_BYTES => "../../res/ref_ve_v0100.wasm",
+    // the audited 0.2.3 release, see "Test fixtures" in README.md
+    V0200_REF_VE_WASM_BYTES => "../../res/ref_ve_v0200.wasm",
     REF_VE_WASM_BYTES => "../../res/ref_ve.wasm",
 
     FUNGIBLE_TOKEN_WASM_BYTES => "../../res/mock_ft.wasm",
@@ -64,6 +68,10 @@ pub fn previous_ref_ve_wasm_bytes() -> &'static [u8] {
     &PREV_REF_VE_WASM_BYTES
 }
 
+pub fn v0200_ref_ve_wasm_bytes() -> &'static [u8] {
+    &V0200_REF_VE_WASM_BYTES
+}
+
 pub fn ref_ve_wasm_bytes() -> &'static [u8] {
     &REF_VE_WASM_BYTES
 }
@@ -77,6 +85,8 @@ pub const OWNER_ID: &str = "owner.near";
 pub const DAY_TS: Timestamp = 60 * 60 * 24 * 1_000_000_000;
 pub const DEFAULT_GAS: Gas = Gas(Gas::ONE_TERA.0 * 15);
 pub const MAX_GAS: Gas = Gas(Gas::ONE_TERA.0 * 300);
+/// Enough for the storage of a proposal with the longest description, the rest is refunded.
+pub const PROPOSAL_STORAGE_DEPOSIT: Balance = 10u128.pow(23);
 pub const TOKEN_DECIMALS: u8 = 24;
 pub const TOKEN_TOTAL_SUPPLY: Balance =
     1_000_000_000 * 10u128.pow(TOKEN_DECIMALS as _);
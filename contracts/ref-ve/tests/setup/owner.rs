@@ -17,6 +17,51 @@ impl Env {
             )
     }
 
+    pub fn set_treasury(
+        &self, 
+        operator: &UserAccount,
+        treasury: &UserAccount,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.set_treasury(
+                    treasury.account_id(),
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn fund_reward_storage_budget(
+        &self, 
+        operator: &UserAccount,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.fund_reward_storage_budget(),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
+    pub fn withdraw_reward_storage_budget(
+        &self, 
+        operator: &UserAccount,
+        amount: u128,
+        deposit: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_reward_storage_budget(
+                    U128(amount),
+                ),
+                MAX_GAS.0,
+                deposit,
+            )
+    }
+
     pub fn extend_operators(
         &self, 
         operator: &UserAccount,
@@ -50,4 +95,38 @@ impl Env {
             deposit,
         )
     }
+
+    pub fn extend_guardians(
+        &self, 
+        operator: &UserAccount,
+        guardians: Vec<&UserAccount>,
+        deposit: u128
+    ) -> ExecutionResult {
+        let guardians = guardians.iter().map(|v| v.account_id()).collect::<Vec<_>>();
+        operator
+        .function_call(
+            self.ve_contract.contract.extend_guardians(
+                guardians,
+            ),
+            MAX_GAS.0,
+            deposit,
+        )
+    }
+
+    pub fn remove_guardians(
+        &self, 
+        operator: &UserAccount,
+        guardians: Vec<&UserAccount>,
+        deposit: u128
+    ) -> ExecutionResult {
+        let guardians = guardians.iter().map(|v| v.account_id()).collect::<Vec<_>>();
+        operator
+        .function_call(
+            self.ve_contract.contract.remove_guardians(
+                guardians,
+            ),
+            MAX_GAS.0,
+            deposit,
+        )
+    }
 }
\ No newline at end of file
@@ -1,5 +1,15 @@
 use crate::*;
+use near_contract_standards::storage_management::StorageBalance;
+use near_sdk::json_types::U128;
+
 impl Env {
+    pub fn storage_balance_of(&self, user: &UserAccount) -> Option<StorageBalance> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.storage_balance_of(user.account_id())
+        ).unwrap_json::<Option<StorageBalance>>()
+    }
+
     pub fn storage_deposit (
         &self,
         operator: &UserAccount,
@@ -20,12 +30,13 @@ impl Env {
     pub fn storage_withdraw(
         &self,
         operator: &UserAccount,
+        amount: Option<u128>,
         deposit: u128
     ) -> ExecutionResult {
         operator
             .function_call(
                 self.ve_contract.contract.storage_withdraw(
-                    None,
+                    amount.map(U128),
                 ),
                 MAX_GAS.0,
                 deposit,
@@ -27,6 +27,53 @@ impl Env {
         )
     }
 
+    pub fn deposit_vesting_reward(
+        &self,
+        token: &UserAccount,
+        user: &UserAccount,
+        amount: Balance,
+        proposal_id: u32,
+        incentive_key: u32,
+        vesting_duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            token.account_id.clone(),
+            "ft_transfer_call",
+            &json!({
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Reward\": {{\"proposal_id\": {}, \"incentive_key\": {}, \"vesting_duration_sec\": {}}}}}", proposal_id, incentive_key, vesting_duration_sec),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
+    pub fn deposit_mft_reward(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        proposal_id: u32,
+        incentive_key: u32,
+    ) -> ExecutionResult {
+        user.call(
+            self.lptoken_contract.account_id(),
+            "mft_transfer_call",
+            &json!({
+                "token_id": &lpt_id(),
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Reward\": {{\"proposal_id\": {}, \"incentive_key\": {}}}}}", proposal_id, incentive_key),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
     pub fn lock_lpt(
         &self,
         user: &UserAccount,
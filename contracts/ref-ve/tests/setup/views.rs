@@ -1,7 +1,7 @@
 use crate::*;
-use near_sdk::AccountId;
 use std::collections::HashMap;
 use near_sdk::json_types::U128;
+use near_sdk_sim::ViewResult;
 
 
 impl Env {
@@ -26,11 +26,45 @@ impl Env {
         ).unwrap_json::<Vec<Proposal>>()
     }
 
+    pub fn list_proposals_by_filter(&self, filter: ProposalFilter, from_proposal_id: Option<u32>, limit: Option<u32>) -> ProposalPage {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.list_proposals_by_filter(Some(filter), from_proposal_id, limit)
+        ).unwrap_json::<ProposalPage>()
+    }
+
+    pub fn get_proposal_result(&self, proposal_id: u32) -> Option<ProposalResult> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_proposal_result(proposal_id)
+        ).unwrap_json::<Option<ProposalResult>>()
+    }
+
     pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal>{
+        match self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_proposal(proposal_id, None)
+        ).unwrap_json::<Option<ProposalInfo>>() {
+            Some(ProposalInfo::Full(proposal)) => Some(proposal),
+            _ => None,
+        }
+    }
+
+    pub fn get_proposal_summary(&self, proposal_id: u32) -> Option<ProposalSummary>{
+        match self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_proposal(proposal_id, Some(true))
+        ).unwrap_json::<Option<ProposalInfo>>() {
+            Some(ProposalInfo::Summary(summary)) => Some(summary),
+            _ => None,
+        }
+    }
+
+    pub fn get_proposal_metadata(&self, proposal_id: u32) -> Option<ProposalMetadata>{
         self.owner
         .view_method_call(
-            self.ve_contract.contract.get_proposal(proposal_id)
-        ).unwrap_json::<Option<Proposal>>()
+            self.ve_contract.contract.get_proposal_metadata(proposal_id)
+        ).unwrap_json::<Option<ProposalMetadata>>()
     }
 
     pub fn get_account_info(&self, user: &UserAccount) -> Option<AccountInfo>{
@@ -54,6 +88,20 @@ impl Env {
         ).unwrap_json::<HashMap<u32, VoteDetail>>()
     }
 
+    pub fn list_vote_detail_history(&self, user: &UserAccount, from_index: Option<u64>, limit: Option<u64>) -> Vec<VoteHistory> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.list_vote_detail_history(user.account_id(), from_index, limit)
+        ).unwrap_json::<Vec<VoteHistory>>()
+    }
+
+    pub fn get_history_stats(&self, user: &UserAccount) -> Option<HistoryStats> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_history_stats(user.account_id())
+        ).unwrap_json::<Option<HistoryStats>>()
+    }
+
     pub fn get_unclaimed_proposal(&self, user: &UserAccount) -> HashMap<u32, VoteDetail> {
         self.owner
         .view_method_call(
@@ -61,17 +109,108 @@ impl Env {
         ).unwrap_json::<HashMap<u32, VoteDetail>>()
     }
 
-    pub fn get_unclaimed_rewards(&self, user: &UserAccount) -> HashMap<AccountId, U128> {
+    pub fn get_unclaimed_rewards(&self, user: &UserAccount) -> HashMap<String, U128> {
         self.owner
         .view_method_call(
             self.ve_contract.contract.get_unclaimed_rewards(user.account_id())
-        ).unwrap_json::<HashMap<AccountId, U128>>()
+        ).unwrap_json::<HashMap<String, U128>>()
     }
 
-    pub fn list_removed_proposal_assets(&self) -> HashMap<AccountId, U128> {
+    pub fn list_removed_proposal_assets(&self) -> HashMap<String, U128> {
         self.owner
         .view_method_call(
             self.ve_contract.contract.list_removed_proposal_assets(None, None)
-        ).unwrap_json::<HashMap<AccountId, U128>>()
+        ).unwrap_json::<HashMap<String, U128>>()
+    }
+
+    pub fn get_incentive_refunds(&self, account: &UserAccount) -> HashMap<String, U128> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_incentive_refunds(account.account_id())
+        ).unwrap_json::<HashMap<String, U128>>()
+    }
+
+    pub fn list_lpt_lostfound(&self) -> HashMap<String, U128> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.list_lpt_lostfound(None, None)
+        ).unwrap_json::<HashMap<String, U128>>()
+    }
+
+    pub fn list_reward_lostfound(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<RewardLostfound> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.list_reward_lostfound(from_index, limit)
+        ).unwrap_json::<Vec<RewardLostfound>>()
+    }
+
+    pub fn list_incentive_deposits(&self, proposal_id: u32, incentive_key: u32, from_index: Option<u64>, limit: Option<u64>) -> Vec<IncentiveDeposit> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.list_incentive_deposits(proposal_id, incentive_key, from_index, limit)
+        ).unwrap_json::<Vec<IncentiveDeposit>>()
+    }
+
+    pub fn list_proposal_schedules(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<ProposalSchedule> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.list_proposal_schedules(from_index, limit)
+        ).unwrap_json::<Vec<ProposalSchedule>>()
+    }
+
+    pub fn get_proposal_schedule(&self, schedule_id: u32) -> Option<ProposalSchedule> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_proposal_schedule(schedule_id)
+        ).unwrap_json::<Option<ProposalSchedule>>()
+    }
+
+    pub fn preview_lock(&self, user: &UserAccount, amount: u128, duration_sec: u32) -> ViewResult {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.preview_lock(user.account_id(), U128(amount), duration_sec)
+        )
+    }
+
+    pub fn preview_append(&self, user: &UserAccount, amount: u128, append_duration_sec: u32) -> ViewResult {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.preview_append(user.account_id(), U128(amount), append_duration_sec)
+        )
+    }
+
+    pub fn preview_withdraw(&self, user: &UserAccount, amount: Option<u128>) -> ViewResult {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.preview_withdraw(user.account_id(), amount.map(U128))
+        )
+    }
+
+    pub fn preview_vote_reward(&self, user: &UserAccount, proposal_id: u32, action: Action) -> ViewResult {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.preview_vote_reward(user.account_id(), proposal_id, action)
+        )
+    }
+
+    pub fn preview_claim(&self, user: &UserAccount) -> ClaimRewardsResult {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.preview_claim(user.account_id())
+        ).unwrap_json::<ClaimRewardsResult>()
+    }
+
+    pub fn get_projected_rewards(&self, user: &UserAccount) -> Vec<ProjectedReward> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_projected_rewards(user.account_id())
+        ).unwrap_json::<Vec<ProjectedReward>>()
+    }
+
+    pub fn list_accounts(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<AccountInfo> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.list_accounts(from_index, limit)
+        ).unwrap_json::<Vec<AccountInfo>>()
     }
-}
\ No newline at end of file
+}
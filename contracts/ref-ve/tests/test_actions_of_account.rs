@@ -65,7 +65,7 @@ fn test_withdraw_lpt() {
     e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
     e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
     e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
-    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
     e.skip_time(DAY_SEC);
     e.action_proposal(&users.alice, 0, Action::VoteApprove, None).assert_success();
     assert_eq!(vec![VoteInfo{
@@ -111,4 +111,221 @@ fn test_withdraw_lpt() {
     assert_eq!(0, e.get_proposal(0).unwrap().ve_amount_at_last_action);
     assert_eq!(HashMap::new(), e.get_vote_detail(&users.alice));
     assert_eq!(HashMap::new(), e.get_vote_detail_history(&users.alice));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_retry_withdraw_lpt() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+
+    // error scene 
+    // 1 : E101_INSUFFICIENT_BALANCE
+    assert_err!(e.retry_withdraw_lpt(&users.alice, 1), E101_INSUFFICIENT_BALANCE);
+
+    e.mft_unregister(&lpt_id(), &users.alice);
+    e.withdraw_lpt(&users.alice, None).assert_success();
+    assert_eq!(to_yocto("100"), e.list_lpt_lostfound().get(users.alice.account_id().as_str()).unwrap().0);
+
+    // 2 : Requires attached deposit of exactly 1 yoctoNEAR
+    assert_err!(e.retry_withdraw_lpt(&users.alice, 0), "Requires attached deposit of exactly 1 yoctoNEAR");
+
+    // still not registered, lpt goes back to lostfound
+    e.retry_withdraw_lpt(&users.alice, 1).assert_success();
+    assert_eq!(to_yocto("100"), e.list_lpt_lostfound().get(users.alice.account_id().as_str()).unwrap().0);
+    assert_eq!(to_yocto("100"), e.get_metadata().lostfound.0);
+
+    e.mft_storage_deposit(&lpt_id(), &users.alice);
+    e.retry_withdraw_lpt(&users.alice, 1).assert_success();
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("100"));
+    assert!(e.list_lpt_lostfound().is_empty());
+    assert_eq!(0, e.get_metadata().lostfound.0);
+}
+
+#[test]
+fn test_prune_proposal_history() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    for _ in 0..3 {
+        e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    }
+    e.skip_time(DAY_SEC);
+    for proposal_id in 0..3 {
+        e.action_proposal(&users.alice, proposal_id, Action::VoteApprove, None).assert_success();
+    }
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 1, 0).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_rewards(&users.alice, vec![0, 1, 2]).assert_success();
+
+    // the history is listed in storage order
+    let ve_lpt_amount = e.get_account_info(&users.alice).unwrap().ve_lpt_amount;
+    let mut proposal_ids: Vec<u32> = e.list_vote_detail_history(&users.alice, None, None).into_iter().map(|item| item.proposal_id).collect();
+    proposal_ids.sort_unstable();
+    assert_eq!(vec![0, 1, 2], proposal_ids);
+    assert_eq!(2, e.list_vote_detail_history(&users.alice, Some(1), None).len());
+    let history = e.list_vote_detail_history(&users.alice, None, Some(1));
+    assert_eq!(1, history.len());
+    assert_eq!(VoteDetail { action: Action::VoteApprove, amount: ve_lpt_amount }, history[0].vote_detail);
+    assert_eq!(HistoryStats {
+        votes_cast: 3,
+        rewards_earned: HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]),
+    }, e.get_history_stats(&users.alice).unwrap());
+
+    // error scene 
+    // 1 : Requires attached deposit of exactly 1 yoctoNEAR
+    assert_err!(e.prune_proposal_history(&users.alice, 2, None, None, 0), "Requires attached deposit of exactly 1 yoctoNEAR");
+    // 2 : E100_ACC_NOT_REGISTERED
+    assert_err!(e.prune_proposal_history(&users.bob, 2, None, None, 1), E100_ACC_NOT_REGISTERED);
+
+    // only the given window is visited
+    assert_eq!(0, e.prune_proposal_history(&users.alice, 2, Some(3), None, 1).unwrap_json::<u32>());
+    let window: Vec<u32> = e.list_vote_detail_history(&users.alice, None, Some(1)).into_iter().map(|item| item.proposal_id).collect();
+    assert_eq!(if window[0] < 2 { 1 } else { 0 }, e.prune_proposal_history(&users.alice, 2, None, Some(1), 1).unwrap_json::<u32>());
+    e.prune_proposal_history(&users.alice, 2, None, None, 1).assert_success();
+    assert_eq!(0, e.prune_proposal_history(&users.alice, 2, None, None, 1).unwrap_json::<u32>());
+    assert_eq!(vec![2], e.list_vote_detail_history(&users.alice, None, None).into_iter().map(|item| item.proposal_id).collect::<Vec<_>>());
+    assert_eq!(3, e.get_history_stats(&users.alice).unwrap().votes_cast);
+
+    // in archival mode only stats are kept
+    e.modify_archive_history(&e.owner, true).assert_success();
+    assert!(e.get_metadata().archive_history);
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 3, Action::VoteApprove, None).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_reward(&users.alice, 3).assert_success();
+    assert_eq!(vec![2], e.list_vote_detail_history(&users.alice, None, None).into_iter().map(|item| item.proposal_id).collect::<Vec<_>>());
+    assert_eq!(4, e.get_history_stats(&users.alice).unwrap().votes_cast);
+}
+
+#[test]
+fn test_previews() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("400"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    // error scene 
+    // 1 : E503_FIRST_LOCK_TOO_FEW
+    assert!(format!("{:?}", e.preview_lock(&users.alice, to_yocto("0.001"), DEFAULT_MAX_LOCKING_DURATION_SEC).unwrap_err()).contains(E503_FIRST_LOCK_TOO_FEW));
+
+    // 2 : E302_INVALID_DURATION
+    assert!(format!("{:?}", e.preview_lock(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC + 1).unwrap_err()).contains(E302_INVALID_DURATION));
+
+    // lock and append match the previews
+    let preview = e.preview_lock(&users.alice, to_yocto("100") + 10, DEFAULT_MAX_LOCKING_DURATION_SEC).unwrap_json::<LockPreview>();
+    assert_eq!(preview.amount, to_yocto("100"));
+    assert_eq!(preview.refund, 10);
+    assert_eq!(preview.increased_ve_lpt, to_ve_token("200"));
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let account_info = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(preview.ve_lpt_amount, account_info.ve_lpt_amount);
+    assert_eq!(preview.unlock_timestamp, account_info.unlock_timestamp);
+
+    let preview = e.preview_append(&users.alice, to_yocto("100"), 0).unwrap_json::<LockPreview>();
+    assert_eq!(preview.increased_ve_lpt, to_ve_token("200"));
+    e.append_lpt(&users.alice, to_yocto("100"), 0).assert_success();
+    assert_eq!(preview.ve_lpt_amount, e.get_account_info(&users.alice).unwrap().ve_lpt_amount);
+
+    // 3 : E305_STILL_IN_LOCK
+    assert!(format!("{:?}", e.preview_withdraw(&users.alice, None).unwrap_err()).contains(E305_STILL_IN_LOCK));
+
+    // vote and claim match the previews
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("300"));
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 2).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+
+    // 4 : E205_NOT_VOTABLE
+    assert!(format!("{:?}", e.preview_vote_reward(&users.alice, 0, Action::VotePoll { poll_id: 0 }).unwrap_err()).contains(E205_NOT_VOTABLE));
+
+    e.skip_time(DAY_SEC);
+    let preview = e.preview_vote_reward(&users.alice, 0, Action::VotePoll { poll_id: 0 }).unwrap_json::<VoteRewardPreview>();
+    assert_eq!(preview.ballots, to_ve_token("400"));
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), preview.rewards);
+    let preview = e.preview_vote_reward(&users.alice, 0, Action::VotePoll { poll_id: 1 }).unwrap_json::<VoteRewardPreview>();
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("200"))]), preview.rewards);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 1 }, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+    e.extend_guardians(&e.owner, vec![&users.charlie], 1).assert_success();
+    e.cancel_proposal(&users.charlie, 1, "malicious".to_string(), 1).assert_success();
+
+    // 5 : E200_ALREADY_VOTED
+    assert!(format!("{:?}", e.preview_vote_reward(&users.alice, 0, Action::VotePoll { poll_id: 1 }).unwrap_err()).contains(E200_ALREADY_VOTED));
+
+    // the cancelled proposal is released right away
+    assert_eq!(vec![1], e.preview_claim(&users.alice).claimed_proposal_ids);
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    let preview = e.preview_claim(&users.alice);
+    assert_eq!(vec![0, 1], preview.claimed_proposal_ids);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("200"))]), preview.rewards);
+    let result: ClaimRewardsResult = e.claim_rewards(&users.alice, vec![0, 1]).unwrap_json();
+    assert_eq!(preview.claimed_proposal_ids, result.claimed_proposal_ids);
+    assert_eq!(preview.rewards, result.rewards);
+    assert_eq!(preview.vesting_rewards.len(), result.vesting_rewards.len());
+    assert_eq!(preview.rewards, e.get_account_info(&users.alice).unwrap().rewards);
+    assert!(e.preview_claim(&users.alice).claimed_proposal_ids.is_empty());
+
+    // withdraw matches the preview
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    let preview = e.preview_withdraw(&users.alice, Some(to_yocto("100"))).unwrap_json::<WithdrawPreview>();
+    assert_eq!(preview.decreased_ve_lpt, to_ve_token("200"));
+    assert_eq!(preview.lpt_amount, to_yocto("100"));
+    e.withdraw_lpt(&users.alice, Some(to_yocto("100"))).assert_success();
+    assert_eq!(preview.ve_lpt_amount, e.get_account_info(&users.alice).unwrap().ve_lpt_amount);
+}
+
+#[test]
+fn test_list_accounts() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("300"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("300"), DEFAULT_MIN_LOCKING_DURATION_SEC).assert_success();
+    e.storage_deposit(&users.charlie, &users.charlie, to_yocto("1")).assert_success();
+
+    let account_ids = |accounts: Vec<AccountInfo>| accounts.into_iter().map(|info| info.account_id).collect::<Vec<_>>();
+    assert_eq!(3, e.get_metadata().indexed_account_count.0);
+    assert_eq!(vec![users.alice.account_id(), users.bob.account_id(), users.charlie.account_id()], account_ids(e.list_accounts(None, None)));
+    assert_eq!(vec![users.bob.account_id()], account_ids(e.list_accounts(Some(1), Some(1))));
+    assert_eq!(to_ve_token("325"), e.list_accounts(Some(1), Some(1))[0].ve_lpt_amount);
+    assert_eq!(vec![users.charlie.account_id()], account_ids(e.list_accounts(Some(2), Some(100))));
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED
+    assert_err!(e.index_accounts(&users.alice, vec![users.alice.account_id()]), E002_NOT_ALLOWED);
+
+    // 2 : E107_TOO_MANY_ACCOUNTS
+    assert_err!(e.index_accounts(&e.owner, vec![users.alice.account_id(); ACCOUNT_INDEX_BATCH_LIMIT + 1]), E107_TOO_MANY_ACCOUNTS);
+
+    // accounts already indexed are skipped
+    assert_eq!(0, e.index_accounts(&e.owner, vec![users.alice.account_id(), users.dude.account_id()]).unwrap_json::<u32>());
+
+    // unregistered accounts leave the index
+    e.storage_unregister(&users.charlie, 1).assert_success();
+    assert_eq!(vec![users.alice.account_id(), users.bob.account_id()], account_ids(e.list_accounts(None, None)));
+}
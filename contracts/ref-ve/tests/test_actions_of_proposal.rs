@@ -1,6 +1,7 @@
 mod setup;
 use crate::setup::*;
 use std::collections::HashMap;
+use near_sdk::json_types::U128;
 
 #[test]
 fn test_create_proposal(){
@@ -17,32 +18,140 @@ fn test_create_proposal(){
     
     // error scene 
     // 1 : E100_ACC_NOT_REGISTERED
-    assert_err!(e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, 1), E100_ACC_NOT_REGISTERED);
+    assert_err!(e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT), E100_ACC_NOT_REGISTERED);
     
     // 2 : E002_NOT_ALLOWED just whitelisted accounts can create proposal 
     e.storage_deposit(&users.bob, &users.bob, to_yocto("1"));
-    assert_err!(e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, 1), E002_NOT_ALLOWED);
+    assert_err!(e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT), E002_NOT_ALLOWED);
 
     // 3 : E402_INVALID_START_TIME 
-    assert_err!(e.create_proposal(&users.alice, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, 1), E402_INVALID_START_TIME);
-    assert_err!(e.create_proposal(&users.alice, ProposalKind::Poll { options: vec![] }, "Poll".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, 1), E402_INVALID_START_TIME);
-    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, 1), E402_INVALID_START_TIME);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT), E402_INVALID_START_TIME);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Poll { options: vec![] }, "Poll".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT), E402_INVALID_START_TIME);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT), E402_INVALID_START_TIME);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time()) - DAY_SEC, DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT), E402_INVALID_START_TIME);
 
     // 4 : E208_DESCRIPTION_TOO_LONG
-    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "a".repeat(2049), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, 1), E208_DESCRIPTION_TOO_LONG);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "a".repeat(2049), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT), E208_DESCRIPTION_TOO_LONG);
 
     // 5 : E302_INVALID_DURATION
-    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC - 1, 1), E302_INVALID_DURATION);
-    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time()), DEFAULT_MAX_VOTING_DURATION_SEC + 1, 1), E302_INVALID_DURATION);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC - 1, PROPOSAL_STORAGE_DEPOSIT), E302_INVALID_DURATION);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time()), DEFAULT_MAX_VOTING_DURATION_SEC + 1, PROPOSAL_STORAGE_DEPOSIT), E302_INVALID_DURATION);
+
+    // 6 : E102_INSUFFICIENT_STORAGE
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1), E102_INSUFFICIENT_STORAGE);
 
     let mut before = e.get_metadata();
-    e.create_proposal(&users.alice, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec![] }, "a".repeat(2048), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::FarmingReward { farm_list: vec![], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    let near_balance_before = users.alice.account().unwrap().amount;
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec![] }, "a".repeat(2048), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    // the deposit beyond the storage used is refunded
+    let storage_deposit = e.get_proposal(1).unwrap().storage_deposit;
+    assert!(storage_deposit > 2048 * 10u128.pow(19));
+    assert!(storage_deposit > e.get_proposal(0).unwrap().storage_deposit);
+    assert!(near_balance_before - users.alice.account().unwrap().amount < PROPOSAL_STORAGE_DEPOSIT);
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
     before.proposal_count = 3.into();
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
 }
 
+#[test]
+fn test_create_proposal_with_metadata(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.storage_deposit(&users.alice, &users.alice, to_yocto("1"));
+
+    let metadata = ProposalMetadata {
+        title: "Adjust the farming reward".to_string(),
+        discussion_link: Some("https://gov.ref.finance/t/100".to_string()),
+        content_uri: Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string()),
+        content_hash: Some("9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08".to_string()),
+    };
+
+    // error scene 
+    // 1 : E215_METADATA_TOO_LONG
+    assert_err!(e.create_proposal_with_metadata(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, ProposalMetadata {
+        title: "a".repeat(257),
+        ..metadata.clone()
+    }), E215_METADATA_TOO_LONG);
+    assert_err!(e.create_proposal_with_metadata(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, ProposalMetadata {
+        content_uri: Some("a".repeat(513)),
+        ..metadata.clone()
+    }), E215_METADATA_TOO_LONG);
+
+    // 2 : E216_INVALID_CONTENT_HASH
+    assert_err!(e.create_proposal_with_metadata(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, ProposalMetadata {
+        content_hash: Some("9f86d081".to_string()),
+        ..metadata.clone()
+    }), E216_INVALID_CONTENT_HASH);
+    assert_err!(e.create_proposal_with_metadata(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, ProposalMetadata {
+        content_hash: Some("z".repeat(64)),
+        ..metadata.clone()
+    }), E216_INVALID_CONTENT_HASH);
+
+    e.create_proposal_with_metadata(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, metadata.clone()).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+
+    let expected = ProposalMetadata {
+        content_hash: Some("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_string()),
+        ..metadata
+    };
+    assert_eq!(Some(expected.clone()), e.get_proposal_metadata(0));
+    assert_eq!(Some(expected), e.get_proposal(0).unwrap().metadata);
+    assert_eq!(None, e.get_proposal_metadata(1));
+    assert_eq!(None, e.get_proposal_metadata(2));
+}
+
+#[test]
+fn test_list_proposals_by_filter(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id(), users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.alice, &users.alice, to_yocto("1"));
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    // error scene 
+    // 1 : E416_INVALID_TAGS
+    assert_err!(e.create_proposal_with_tags(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, vec![""]), E416_INVALID_TAGS);
+    assert_err!(e.create_proposal_with_tags(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, vec!["farm list"]), E416_INVALID_TAGS);
+    assert_err!(e.create_proposal_with_tags(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, vec!["a", "b", "c", "d", "e", "f"]), E416_INVALID_TAGS);
+
+    let poll = ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] };
+    e.create_proposal_with_tags(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, vec!["Treasury", "treasury"]).assert_success();
+    e.create_proposal_with_tags(&users.dude, poll.clone(), "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, vec!["treasury", "farm"]).assert_success();
+    e.create_proposal(&users.alice, poll.clone(), "Poll".to_string(), to_sec(e.current_time() + 5 * DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal_with_tags(&users.alice, poll, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, vec!["farm"]).assert_success();
+    assert_eq!(e.get_proposal(0).unwrap().tags, vec!["treasury".to_string()]);
+
+    let ids = |page: ProposalPage| page.proposals.iter().map(|proposal| proposal.id).collect::<Vec<_>>();
+    assert_eq!(vec![3, 2, 1, 0], ids(e.list_proposals_by_filter(ProposalFilter::default(), None, None)));
+    assert_eq!(vec![3, 2, 0], ids(e.list_proposals_by_filter(ProposalFilter { proposer: Some(users.alice.account_id()), ..Default::default() }, None, None)));
+    assert_eq!(vec![1, 0], ids(e.list_proposals_by_filter(ProposalFilter { tag: Some("Treasury".to_string()), ..Default::default() }, None, None)));
+    assert_eq!(vec![3, 2, 1], ids(e.list_proposals_by_filter(ProposalFilter { kind: Some("Poll".to_string()), ..Default::default() }, None, None)));
+    assert_eq!(vec![3], ids(e.list_proposals_by_filter(ProposalFilter { proposer: Some(users.alice.account_id()), tag: Some("farm".to_string()), ..Default::default() }, None, None)));
+
+    // cursor
+    let page = e.list_proposals_by_filter(ProposalFilter { kind: Some("Poll".to_string()), ..Default::default() }, None, Some(2));
+    assert_eq!(Some(1), page.next_cursor);
+    assert_eq!(vec![3, 2], ids(page));
+    let page = e.list_proposals_by_filter(ProposalFilter { kind: Some("Poll".to_string()), ..Default::default() }, Some(1), Some(2));
+    assert_eq!(None, page.next_cursor);
+    assert_eq!(vec![1], ids(page));
+
+    // status, removed proposals are skipped
+    e.skip_time(2 * DAY_SEC);
+    assert_eq!(vec![2], ids(e.list_proposals_by_filter(ProposalFilter { status: Some(ProposalStatus::WarmUp), ..Default::default() }, None, None)));
+    assert_eq!(vec![3, 1, 0], ids(e.list_proposals_by_filter(ProposalFilter { status: Some(ProposalStatus::InProgress), ..Default::default() }, None, None)));
+    assert_eq!(e.remove_proposal(&users.alice, 2).unwrap_json::<bool>(), true);
+    assert_eq!(vec![3, 0], ids(e.list_proposals_by_filter(ProposalFilter { proposer: Some(users.alice.account_id()), ..Default::default() }, None, None)));
+    // removed proposals are dropped from the indexes
+    let page = e.list_proposals_by_filter(ProposalFilter { kind: Some("Poll".to_string()), ..Default::default() }, None, Some(1));
+    assert_eq!(Some(1), page.next_cursor);
+    assert_eq!(vec![3], ids(page));
+}
+
 #[test]
 fn test_remove_proposal(){
     let e = init_env();
@@ -55,23 +164,208 @@ fn test_remove_proposal(){
     e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id(), users.dude.account_id()]).assert_success();
 
     e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
-    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "a".repeat(2048), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
     
     // error scene 
     // 1 : E100_ACC_NOT_REGISTERED
     assert_err!(e.remove_proposal(&users.alice, 0), E002_NOT_ALLOWED);
     assert_err!(e.remove_proposal(&users.dude, 1), E002_NOT_ALLOWED);
 
-    // success
+    // success, the storage deposit is refunded
     let mut before = e.get_metadata();
+    let near_balance_before = users.alice.account().unwrap().amount;
     assert_eq!(e.remove_proposal(&users.alice, 1).unwrap_json::<bool>(), true);
+    assert!(users.alice.account().unwrap().amount > near_balance_before);
     e.skip_time(DAY_SEC);
     assert_eq!(e.remove_proposal(&users.dude, 0).unwrap_json::<bool>(), false);
     before.proposal_count.0 -= 1;
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
 }
 
+#[test]
+fn test_remove_proposal_refund_incentive(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id(), tokens.wnear.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("300"));
+    e.ft_mint(&tokens.wnear, &users.bob, to_yocto("300"));
+    e.ft_mint(&tokens.nref, &users.charlie, to_yocto("300"));
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 1).assert_success();
+    e.deposit_reward(&tokens.wnear, &users.bob, to_yocto("100"), 0, 1).assert_success();
+    e.deposit_reward(&tokens.nref, &users.charlie, to_yocto("50"), 0, 0).assert_success();
+
+    assert_eq!(e.remove_proposal(&users.dude, 0).unwrap_json::<bool>(), true);
+    assert!(e.list_removed_proposal_assets().is_empty());
+    assert!(e.list_incentive_deposits(0, 0, None, None).is_empty());
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), U128(to_yocto("200"))), (tokens.wnear.account_id().to_string(), U128(to_yocto("100")))]), e.get_incentive_refunds(&users.bob));
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), U128(to_yocto("50")))]), e.get_incentive_refunds(&users.charlie));
+
+    // error scene 
+    // 1 : E101_INSUFFICIENT_BALANCE
+    assert_err!(e.withdraw_incentive_refund(&users.charlie, &tokens.wnear), E101_INSUFFICIENT_BALANCE);
+
+    // failed transfer is credited back
+    e.ft_storage_unregister(&tokens.nref, &users.charlie);
+    e.withdraw_incentive_refund(&users.charlie, &tokens.nref).assert_success();
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), U128(to_yocto("50")))]), e.get_incentive_refunds(&users.charlie));
+
+    e.ft_storage_deposit(&users.charlie, &tokens.nref);
+    e.withdraw_incentive_refund(&users.charlie, &tokens.nref).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.charlie), to_yocto("50"));
+    assert!(e.get_incentive_refunds(&users.charlie).is_empty());
+
+    e.withdraw_incentive_refund(&users.bob, &tokens.nref).assert_success();
+    e.withdraw_incentive_refund(&users.bob, &tokens.wnear).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), to_yocto("300"));
+    assert_eq!(e.ft_balance_of(&tokens.wnear, &users.bob), to_yocto("300"));
+    assert!(e.get_incentive_refunds(&users.bob).is_empty());
+}
+
+#[test]
+fn test_cancel_proposal(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_guardians(&e.owner, vec![&users.charlie], 1).assert_success();
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("300"));
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 1).assert_success();
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+
+    // error scene 
+    // 1 : Requires attached deposit of exactly 1 yoctoNEAR
+    assert_err!(e.cancel_proposal(&users.charlie, 0, "malicious".to_string(), 0), "Requires attached deposit of exactly 1 yoctoNEAR");
+
+    // 2 : E002_NOT_ALLOWED
+    assert_err!(e.cancel_proposal(&e.owner, 0, "malicious".to_string(), 1), E002_NOT_ALLOWED);
+    assert_err!(e.cancel_proposal(&users.dude, 0, "malicious".to_string(), 1), E002_NOT_ALLOWED);
+
+    // 3 : E208_DESCRIPTION_TOO_LONG
+    assert_err!(e.cancel_proposal(&users.charlie, 0, "a".repeat(2049), 1), E208_DESCRIPTION_TOO_LONG);
+
+    // success, incentives are refunded and the voter is released without rewards
+    e.cancel_proposal(&users.charlie, 0, "malicious".to_string(), 1).assert_success();
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!(proposal.status, Some(ProposalStatus::Cancelled));
+    assert_eq!(proposal.cancellation.unwrap().reason, "malicious".to_string());
+    assert!(proposal.incentive.is_empty());
+    assert!(e.list_incentive_deposits(0, 0, None, None).is_empty());
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), U128(to_yocto("200")))]), e.get_incentive_refunds(&users.bob));
+    assert!(!e.get_vote_detail(&users.alice).contains_key(&0));
+
+    // 4 : E414_PROPOSAL_CANCELLED
+    assert_err!(e.cancel_proposal(&users.charlie, 0, "malicious".to_string(), 1), E414_PROPOSAL_CANCELLED);
+
+    // a cancelled proposal could be archived once every voter is released
+    assert_err!(e.archive_proposal(&users.charlie, 0), E212_REWARD_NOT_ALL_CLAIMED);
+    e.claim_reward(&users.alice, 0).assert_success();
+    assert!(e.list_vote_detail_history(&users.alice, None, None).is_empty());
+    assert_eq!(e.get_history_stats(&users.alice).unwrap().votes_cast, 0);
+    assert!(e.get_account_info(&users.alice).unwrap().rewards.is_empty());
+    assert_eq!(1, e.get_proposal(0).unwrap().claimed_count);
+    let near_balance_before = users.dude.account().unwrap().amount;
+    e.archive_proposal(&users.charlie, 0).assert_success();
+    assert!(e.get_proposal(0).is_none());
+    assert!(e.get_proposal_summary(0).is_some());
+    // the storage released goes back to the proposer
+    assert!(users.dude.account().unwrap().amount > near_balance_before);
+
+    // 5 : E415_PROPOSAL_CLAIMED
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_reward(&users.alice, 1).assert_success();
+    assert_err!(e.cancel_proposal(&users.charlie, 1, "malicious".to_string(), 1), E415_PROPOSAL_CLAIMED);
+}
+
+#[test]
+fn test_update_proposal(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id(), users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["Yes".to_string(), "No".to_string(), "Abstain".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("300"));
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 1).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 3).assert_success();
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED
+    assert_err!(e.update_proposal(&users.alice, 0, Some("Alice".to_string()), None, None, None, 1), E002_NOT_ALLOWED);
+
+    // 2 : E102_INSUFFICIENT_STORAGE
+    assert_err!(e.update_proposal(&users.dude, 0, None, None, None, None, 0), E102_INSUFFICIENT_STORAGE);
+    assert_err!(e.update_proposal(&users.dude, 0, Some("a".repeat(1024)), None, None, None, 1), E102_INSUFFICIENT_STORAGE);
+
+    // 3 : E208_DESCRIPTION_TOO_LONG
+    assert_err!(e.update_proposal(&users.dude, 0, Some("a".repeat(2049)), None, None, None, 1), E208_DESCRIPTION_TOO_LONG);
+
+    // 4 : E302_INVALID_DURATION
+    assert_err!(e.update_proposal(&users.dude, 0, None, None, None, Some(DEFAULT_MAX_VOTING_DURATION_SEC + 1), 1), E302_INVALID_DURATION);
+
+    // 5 : E402_INVALID_START_TIME
+    assert_err!(e.update_proposal(&users.dude, 0, None, None, Some(to_sec(e.current_time())), None, 1), E402_INVALID_START_TIME);
+    assert_err!(e.update_proposal(&users.dude, 0, None, None, Some(to_sec(e.current_time()) - DAY_SEC), None, 1), E402_INVALID_START_TIME);
+
+    // 6 : E413_INVALID_OPTIONS
+    assert_err!(e.update_proposal(&users.dude, 1, None, Some(vec!["Yes".to_string()]), None, None, 1), E413_INVALID_OPTIONS);
+
+    // success, option "Yes" is kept, option "Abstain" is replaced
+    let storage_deposit = e.get_proposal(0).unwrap().storage_deposit;
+    let start_at = to_sec(e.current_time() + 2 * DAY_TS);
+    assert_eq!(e.update_proposal(&users.dude, 0, Some("Poll v2".to_string()), Some(vec!["Yes".to_string(), "No".to_string(), "Veto".to_string()]), Some(start_at), None, PROPOSAL_STORAGE_DEPOSIT).unwrap_json::<u32>(), 1);
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!(proposal.revision, 1);
+    assert_eq!(proposal.description, "Poll v2".to_string());
+    assert_eq!(proposal.start_at, to_nano(start_at));
+    assert_eq!(proposal.end_at, to_nano(start_at + DEFAULT_MIN_VOTING_DURATION_SEC));
+    assert_eq!(proposal.votes.len(), 3);
+    assert!(proposal.incentive.contains_key(&0));
+    assert!(proposal.incentive.contains_key(&1));
+    assert!(!proposal.incentive.contains_key(&3));
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), U128(to_yocto("100")))]), e.get_incentive_refunds(&users.bob));
+    // the proposer pays for the incentive refund credited
+    assert!(proposal.storage_deposit > storage_deposit);
+
+    // shrinking the options drops the incentives beyond them
+    assert_eq!(e.update_proposal(&users.dude, 0, None, Some(vec!["Maybe".to_string()]), None, None, 1).unwrap_json::<u32>(), 2);
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!(proposal.votes.len(), 1);
+    assert!(proposal.incentive.contains_key(&0));
+    assert!(!proposal.incentive.contains_key(&1));
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), U128(to_yocto("200")))]), e.get_incentive_refunds(&users.bob));
+
+    // 7 : E412_PROPOSAL_NOT_WARMUP
+    e.skip_time(3 * DAY_SEC);
+    assert_err!(e.update_proposal(&users.dude, 0, Some("Poll v3".to_string()), None, None, None, 1), E412_PROPOSAL_NOT_WARMUP);
+}
+
 #[test]
 fn test_action_proposal(){
     let e = init_env();
@@ -84,9 +378,9 @@ fn test_action_proposal(){
 
     e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
     
-    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.bob, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
 
     // error scene 
     // 1 : E100_ACC_NOT_REGISTERED
@@ -237,7 +531,7 @@ fn test_action_proposal_farming_reward_01(){
     
     e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
 
-    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
 
     e.skip_time(DAY_SEC);
 
@@ -270,7 +564,7 @@ fn test_action_proposal_farming_reward_02(){
     
     e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
 
-    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
 
     e.skip_time(DAY_SEC);
 
@@ -305,7 +599,7 @@ fn test_action_proposal_farming_reward_03(){
     
     e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
 
-    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 3 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 3 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
 
     e.skip_time(DAY_SEC);
 
@@ -344,7 +638,7 @@ fn test_action_proposal_farming_reward_04(){
     
     e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
 
-    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 3 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 3 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
 
     e.skip_time(DAY_SEC);
 
@@ -383,7 +677,7 @@ fn test_action_proposal_farming_reward_05(){
     
     e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
 
-    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 10 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 10 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
 
     e.skip_time(DAY_SEC);
 
@@ -405,6 +699,63 @@ fn test_action_proposal_farming_reward_05(){
     assert_eq!(to_ve_token("700"), e.get_proposal(0).unwrap().ve_amount_at_last_action);
 }
 
+#[test]
+fn test_get_proposal_result(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    for (user, amount) in [(&users.alice, "100"), (&users.bob, "100"), (&users.charlie, "50")] {
+        e.mft_mint(&lpt_inner_id(), user, to_yocto("200"));
+        e.lock_lpt(user, to_yocto(amount), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    }
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string(), "topic3".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    assert!(e.get_proposal_result(4).is_none());
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+    e.action_proposal(&users.charlie, 0, Action::VoteFarm { farm_id: 1 }, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VotePoll { poll_id: 1 }, None).assert_success();
+    e.action_proposal(&users.bob, 1, Action::VotePoll { poll_id: 2 }, None).assert_success();
+    e.action_proposal(&users.charlie, 1, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.alice, 2, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.charlie, 2, Action::VoteReject, None).assert_success();
+
+    let result = e.get_proposal_result(0).unwrap();
+    assert_eq!(result.outcome, ProposalOutcome::Pending);
+    assert_eq!(result.total_ballots, to_ve_token("500"));
+    assert_eq!(result.ve_amount_at_last_action, to_ve_token("500"));
+    assert_eq!(result.turnout_bps, 10000);
+    assert_eq!(result.participants, 3);
+    assert_eq!(result.winner, Some(0));
+    assert_eq!(result.ranking, vec![0, 1]);
+    assert_eq!(result.options.iter().map(|option| option.share_bps).collect::<Vec<_>>(), vec![8000, 2000]);
+    assert_eq!(result.options.iter().map(|option| option.farm_reward).collect::<Vec<_>>(), vec![Some(16000), Some(4000)]);
+    assert_eq!(result.options[0].participants, 2);
+    assert_eq!(result.options[1].name, "nusdt.near|nusdc.near|ndai.near&1910".to_string());
+
+    let result = e.get_proposal_result(2).unwrap();
+    assert_eq!(result.turnout_bps, 6000);
+    assert_eq!(result.options.iter().map(|option| option.name.clone()).collect::<Vec<_>>(), vec!["Approve".to_string(), "Reject".to_string(), "Nonsense".to_string()]);
+    assert_eq!(result.options[0].farm_reward, None);
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_eq!(e.get_proposal_result(0).unwrap().outcome, ProposalOutcome::Allocated);
+    let result = e.get_proposal_result(1).unwrap();
+    assert_eq!(result.ranking, vec![1, 2, 0]);
+    assert_eq!(result.winner, None);
+    assert_eq!(result.outcome, ProposalOutcome::Tied { options: vec![1, 2] });
+    assert_eq!(e.get_proposal_result(2).unwrap().outcome, ProposalOutcome::Approved);
+    assert_eq!(e.get_proposal_result(3).unwrap().outcome, ProposalOutcome::NoVotes);
+}
+
 #[test]
 fn test_action_cancel(){
     let e = init_env();
@@ -417,9 +768,9 @@ fn test_action_cancel(){
     
     e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
 
-    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.bob, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
 
     e.skip_time(DAY_SEC);
 
@@ -526,4 +877,181 @@ fn test_action_cancel(){
     assert_eq!(HashMap::from([(2, VoteDetail {
         action: Action::VoteReject, amount: to_ve_token("200")
     })]), e.get_vote_detail_history(&users.alice));
-}
\ No newline at end of file
+}
+#[test]
+fn test_proposal_schedule(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.extend_operators(&e.owner, vec![&users.operator], 1).assert_success();
+
+    let start_at = to_sec(e.current_time() + 2 * DAY_TS);
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED only whitelisted operators can register schedule
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC, 1), E002_NOT_ALLOWED);
+    e.extend_whitelisted_accounts(&e.owner, vec![users.operator.account_id(), users.alice.account_id()]).assert_success();
+    assert_err!(e.register_proposal_schedule(&users.alice, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC, 1), E002_NOT_ALLOWED);
+
+    // 2 : E100_ACC_NOT_REGISTERED proposer must be able to create proposals
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC, 1), E100_ACC_NOT_REGISTERED);
+    e.storage_deposit(&users.operator, &users.operator, to_yocto("1"));
+
+    // 3 : E407_INVALID_SCHEDULE
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, DEFAULT_MIN_VOTING_DURATION_SEC - 1, DAY_SEC, 1), E407_INVALID_SCHEDULE);
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC - 1, 1), E407_INVALID_SCHEDULE);
+    e.modify_voting_duration_limit(&e.owner, 0, DEFAULT_MAX_VOTING_DURATION_SEC).assert_success();
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, 0, 0, DAY_SEC, 1), E407_INVALID_SCHEDULE);
+    e.modify_voting_duration_limit(&e.owner, DEFAULT_MIN_VOTING_DURATION_SEC, DEFAULT_MAX_VOTING_DURATION_SEC).assert_success();
+
+    // 4 : E402_INVALID_START_TIME
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), to_sec(e.current_time()), DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC, 1), E402_INVALID_START_TIME);
+
+    // 5 : E417_SCHEDULE_OVERFLOW
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), u32::MAX - DAY_SEC, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC, 1), E417_SCHEDULE_OVERFLOW);
+
+    // 6 : E102_INSUFFICIENT_STORAGE the registrant pays for the storage of the schedule
+    assert_err!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC, 1), E102_INSUFFICIENT_STORAGE);
+
+    assert_eq!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, DAY_SEC, 2 * PROPOSAL_STORAGE_DEPOSIT).unwrap_json::<u32>(), 0);
+    assert_eq!(1, e.list_proposal_schedules(None, None).len());
+    let storage_balance = e.get_proposal_schedule(0).unwrap().storage_balance;
+    let schedule_storage_cost = 2 * PROPOSAL_STORAGE_DEPOSIT - storage_balance;
+    assert!(schedule_storage_cost > 0);
+
+    // not due yet
+    assert!(e.materialize_scheduled_proposals(&users.alice).unwrap_json::<Vec<u32>>().is_empty());
+
+    e.skip_time(DAY_SEC);
+    assert_eq!(vec![0], e.materialize_scheduled_proposals(&users.alice).unwrap_json::<Vec<u32>>());
+    assert!(e.materialize_scheduled_proposals(&users.alice).unwrap_json::<Vec<u32>>().is_empty());
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!("Round 1".to_string(), proposal.description);
+    assert_eq!(users.operator.account_id(), proposal.proposer);
+    assert_eq!(to_nano(start_at), proposal.start_at);
+    let schedule = e.get_proposal_schedule(0).unwrap();
+    assert_eq!(1, schedule.rounds);
+    assert_eq!(Some(0), schedule.last_proposal_id);
+    assert_eq!(start_at + 14 * DAY_SEC, schedule.next_start_at);
+    // rounds are paid by the storage balance of the schedule
+    assert!(proposal.storage_deposit > 0);
+    assert_eq!(storage_balance - proposal.storage_deposit, schedule.storage_balance);
+
+    // missed round would be skipped
+    e.skip_time(28 * DAY_SEC);
+    // schedules out of the visited range are left for the next call
+    assert!(e.materialize_scheduled_proposals_in_range(&users.alice, Some(1), None).unwrap_json::<Vec<u32>>().is_empty());
+    assert!(e.materialize_scheduled_proposals_in_range(&users.alice, Some(0), Some(0)).unwrap_json::<Vec<u32>>().is_empty());
+    assert_eq!(vec![1], e.materialize_scheduled_proposals(&users.alice).unwrap_json::<Vec<u32>>());
+    let proposal = e.get_proposal(1).unwrap();
+    assert_eq!("Round 2".to_string(), proposal.description);
+    assert_eq!(to_nano(start_at + 28 * DAY_SEC), proposal.start_at);
+
+    // an invalid schedule is skipped instead of failing the whole call
+    e.remove_whitelisted_accounts(&e.owner, vec![users.operator.account_id()]).assert_success();
+    e.skip_time(14 * DAY_SEC);
+    assert!(e.materialize_scheduled_proposals(&users.alice).unwrap_json::<Vec<u32>>().is_empty());
+    e.extend_whitelisted_accounts(&e.owner, vec![users.operator.account_id()]).assert_success();
+    assert_eq!(vec![2], e.materialize_scheduled_proposals(&users.alice).unwrap_json::<Vec<u32>>());
+
+    // lead_sec is clamped to a raised min start vote offset
+    e.modify_min_start_vote_offset_sec(&e.owner, 2 * DAY_SEC).assert_success();
+    e.skip_time(13 * DAY_SEC);
+    assert_eq!(vec![3], e.materialize_scheduled_proposals(&users.alice).unwrap_json::<Vec<u32>>());
+    assert_eq!(to_nano(start_at + 56 * DAY_SEC), e.get_proposal(3).unwrap().start_at);
+
+    // a round the storage balance can't cover is skipped until topped up
+    let start_at = to_sec(e.current_time() + 3 * DAY_TS);
+    assert_eq!(e.register_proposal_schedule(&users.operator, ProposalKind::Common, "Round {round}".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 14 * DAY_SEC, 2 * DAY_SEC, schedule_storage_cost + 1).unwrap_json::<u32>(), 1);
+    assert_eq!(1, e.get_proposal_schedule(1).unwrap().storage_balance);
+    e.skip_time(DAY_SEC);
+    assert!(e.materialize_scheduled_proposals_in_range(&users.alice, Some(1), None).unwrap_json::<Vec<u32>>().is_empty());
+    assert_eq!(0, e.get_proposal_schedule(1).unwrap().rounds);
+    assert_err!(e.top_up_proposal_schedule(&users.alice, 1, PROPOSAL_STORAGE_DEPOSIT), E002_NOT_ALLOWED);
+    assert_err!(e.top_up_proposal_schedule(&users.operator, 2, PROPOSAL_STORAGE_DEPOSIT), E408_SCHEDULE_NOT_EXIST);
+    e.top_up_proposal_schedule(&users.operator, 1, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    assert_eq!(vec![4], e.materialize_scheduled_proposals_in_range(&users.alice, Some(1), None).unwrap_json::<Vec<u32>>());
+    assert_eq!(PROPOSAL_STORAGE_DEPOSIT + 1 - e.get_proposal(4).unwrap().storage_deposit, e.get_proposal_schedule(1).unwrap().storage_balance);
+
+    // remove schedule, the storage balance goes back to the proposer
+    assert_err!(e.remove_proposal_schedule(&users.alice, 0), E002_NOT_ALLOWED);
+    let storage_balance = e.get_proposal_schedule(0).unwrap().storage_balance;
+    let near_balance_before = users.operator.account().unwrap().amount;
+    e.remove_proposal_schedule(&users.operator, 0).assert_success();
+    assert!(users.operator.account().unwrap().amount > near_balance_before + storage_balance - to_yocto("0.01"));
+    assert!(e.list_proposal_schedules(None, None).is_empty());
+    assert_err!(e.remove_proposal_schedule(&users.operator, 0), E408_SCHEDULE_NOT_EXIST);
+}
+
+#[test]
+fn test_archive_proposal() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("50"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+    e.set_treasury(&e.owner, &users.eve, 1).assert_success();
+    e.ft_storage_deposit(&users.eve, &tokens.nref);
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 1 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+
+    // error scene 
+    // 1 : E409_PROPOSAL_NOT_EXPIRED
+    assert_err!(e.archive_proposal(&users.charlie, 0), E409_PROPOSAL_NOT_EXPIRED);
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+
+    // 2 : E212_REWARD_NOT_ALL_CLAIMED
+    e.claim_reward(&users.alice, 0).assert_success();
+    assert_err!(e.archive_proposal(&users.charlie, 0), E212_REWARD_NOT_ALL_CLAIMED);
+    // 3 : E410_PROPOSAL_NOT_SETTLED, the dust must be swept first
+    e.claim_reward(&users.bob, 0).assert_success();
+    assert_err!(e.archive_proposal(&users.charlie, 0), E410_PROPOSAL_NOT_SETTLED);
+    e.sweep_incentive_dust(&users.charlie, 0).assert_success();
+
+    let summary = e.get_proposal_summary(0).unwrap();
+    assert_eq!("Poll".to_string(), summary.kind);
+    assert_eq!(Some(1), summary.outcome);
+    assert_eq!(2, summary.participants);
+    assert!(e.get_proposal(0).is_some());
+
+    let storage_before = e.ve_contract.user_account.account().unwrap().storage_usage;
+    let storage_deposit = e.get_proposal(0).unwrap().storage_deposit;
+    let near_balance_before = users.dude.account().unwrap().amount;
+    assert_eq!(summary, e.archive_proposal(&users.charlie, 0).unwrap_json::<ProposalSummary>());
+    assert!(e.ve_contract.user_account.account().unwrap().storage_usage < storage_before);
+    // the storage released goes back to the proposer, the summary keeps the rest
+    assert!(users.dude.account().unwrap().amount > near_balance_before);
+    assert!(users.dude.account().unwrap().amount < near_balance_before + storage_deposit);
+    assert!(e.get_proposal(0).is_none());
+    assert_eq!(summary, e.get_proposal_summary(0).unwrap());
+    assert!(e.list_proposals(None, None).is_empty());
+    assert_eq!(1, e.get_metadata().proposal_count.0);
+
+    // 4 : E411_PROPOSAL_ARCHIVED
+    assert_err!(e.archive_proposal(&users.charlie, 0), E411_PROPOSAL_ARCHIVED);
+    assert_err!(e.sweep_incentive_dust(&users.charlie, 0), E411_PROPOSAL_ARCHIVED);
+    assert_err!(e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0), E411_PROPOSAL_ARCHIVED);
+
+    // a proposal without dust is settled by archiving it
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_reward(&users.alice, 1).assert_success();
+    assert!(!e.get_proposal(1).unwrap().is_settled);
+    e.archive_proposal(&users.charlie, 1).assert_success();
+    assert!(e.get_proposal(1).is_none());
+}
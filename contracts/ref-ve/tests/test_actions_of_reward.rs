@@ -16,10 +16,10 @@ fn test_claim_and_withdraw_all() {
 
     e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
 
-    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
     e.skip_time(DAY_SEC);
     e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
     e.action_proposal(&users.alice, 1, Action::VotePoll { poll_id: 0 }, None).assert_success();
@@ -55,8 +55,8 @@ fn test_claim_and_withdraw_all() {
 
     e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
 
-    assert_eq!(to_yocto("200"), e.get_unclaimed_rewards(&users.alice).get(&tokens.nref.account_id()).unwrap().0);
-    assert_eq!(to_yocto("200"), e.get_unclaimed_rewards(&users.alice).get(&tokens.wnear.account_id()).unwrap().0);
+    assert_eq!(to_yocto("200"), e.get_unclaimed_rewards(&users.alice).get(tokens.nref.account_id().as_str()).unwrap().0);
+    assert_eq!(to_yocto("200"), e.get_unclaimed_rewards(&users.alice).get(tokens.wnear.account_id().as_str()).unwrap().0);
 
     assert_eq!(HashMap::new(), e.get_vote_detail(&users.alice));
     assert_eq!(HashMap::from([(0, VoteDetail{
@@ -119,13 +119,13 @@ fn test_claim_reward() {
     e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
     e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
 
-    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic11".to_string(), "topic22".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic111".to_string(), "topic222".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic111".to_string(), "topic222".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic11".to_string(), "topic22".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic111".to_string(), "topic222".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic111".to_string(), "topic222".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
    
     e.skip_time(DAY_SEC);
     e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
@@ -189,39 +189,39 @@ fn test_claim_reward() {
 
     assert_eq!(HashMap::new(), e.get_account_info(&users.alice).unwrap().rewards);
     e.claim_reward(&users.alice, 0);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
     e.claim_reward(&users.alice, 1);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("150"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("150"))]), e.get_account_info(&users.alice).unwrap().rewards);
     e.claim_reward(&users.alice, 1);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("150"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("150"))]), e.get_account_info(&users.alice).unwrap().rewards);
     e.claim_reward(&users.alice, 2);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("200")), (tokens.noct.account_id(), to_yocto("50"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("200")), (tokens.noct.account_id().to_string(), to_yocto("50"))]), e.get_account_info(&users.alice).unwrap().rewards);
     e.claim_reward(&users.alice, 3);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("200")), (tokens.noct.account_id(), to_yocto("50"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("200")), (tokens.noct.account_id().to_string(), to_yocto("50"))]), e.get_account_info(&users.alice).unwrap().rewards);
     e.claim_reward(&users.bob, 1);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("50"))]), e.get_account_info(&users.bob).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("50"))]), e.get_account_info(&users.bob).unwrap().rewards);
     e.claim_reward(&users.bob, 2);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("100"))]), e.get_account_info(&users.bob).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), e.get_account_info(&users.bob).unwrap().rewards);
     e.claim_reward(&users.charlie, 2);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("40"))]), e.get_account_info(&users.charlie).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("40"))]), e.get_account_info(&users.charlie).unwrap().rewards);
     e.claim_reward(&users.eve, 2);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("80"))]), e.get_account_info(&users.eve).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("80"))]), e.get_account_info(&users.eve).unwrap().rewards);
     e.claim_reward(&users.charlie, 3);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("80"))]), e.get_account_info(&users.charlie).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("80"))]), e.get_account_info(&users.charlie).unwrap().rewards);
     e.claim_reward(&users.eve, 3);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("160"))]), e.get_account_info(&users.eve).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("160"))]), e.get_account_info(&users.eve).unwrap().rewards);
     e.claim_reward(&users.charlie, 4);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("80")), (tokens.ndai.account_id(), to_yocto("70"))]), e.get_account_info(&users.charlie).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("80")), (tokens.ndai.account_id().to_string(), to_yocto("70"))]), e.get_account_info(&users.charlie).unwrap().rewards);
     e.claim_reward(&users.eve, 4);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("160")), (tokens.ndai.account_id(), to_yocto("140"))]), e.get_account_info(&users.eve).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("160")), (tokens.ndai.account_id().to_string(), to_yocto("140"))]), e.get_account_info(&users.eve).unwrap().rewards);
     e.claim_reward(&users.charlie, 5);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("80")), (tokens.ndai.account_id(), to_yocto("70")), (tokens.wnear.account_id(), to_yocto("70"))]), e.get_account_info(&users.charlie).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("80")), (tokens.ndai.account_id().to_string(), to_yocto("70")), (tokens.wnear.account_id().to_string(), to_yocto("70"))]), e.get_account_info(&users.charlie).unwrap().rewards);
     e.claim_reward(&users.eve, 5);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("160")), (tokens.ndai.account_id(), to_yocto("140")), (tokens.wnear.account_id(), to_yocto("140"))]), e.get_account_info(&users.eve).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("160")), (tokens.ndai.account_id().to_string(), to_yocto("140")), (tokens.wnear.account_id().to_string(), to_yocto("140"))]), e.get_account_info(&users.eve).unwrap().rewards);
     e.claim_reward(&users.charlie, 6);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("80")), (tokens.ndai.account_id(), to_yocto("70")), (tokens.wnear.account_id(), to_yocto("130"))]), e.get_account_info(&users.charlie).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("80")), (tokens.ndai.account_id().to_string(), to_yocto("70")), (tokens.wnear.account_id().to_string(), to_yocto("130"))]), e.get_account_info(&users.charlie).unwrap().rewards);
     e.claim_reward(&users.eve, 6);
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("160")), (tokens.ndai.account_id(), to_yocto("140")), (tokens.wnear.account_id(), to_yocto("260"))]), e.get_account_info(&users.eve).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("160")), (tokens.ndai.account_id().to_string(), to_yocto("140")), (tokens.wnear.account_id().to_string(), to_yocto("260"))]), e.get_account_info(&users.eve).unwrap().rewards);
     assert_eq!(e.get_proposal(0).unwrap().incentive.get(&0).unwrap().incentive_amounts, e.get_proposal(0).unwrap().incentive.get(&0).unwrap().claimed_amounts);
     assert_eq!(e.get_proposal(1).unwrap().incentive.get(&0).unwrap().incentive_amounts, e.get_proposal(1).unwrap().incentive.get(&0).unwrap().claimed_amounts);
     assert_eq!(vec![VoteInfo{
@@ -238,6 +238,56 @@ fn test_claim_reward() {
     assert_eq!(to_ve_token("1000"), e.get_proposal(2).unwrap().ve_amount_at_last_action);
 }
 
+#[test]
+fn test_claim_option_reward() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string(), "topic3".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VotePoll { poll_id: 1 }, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.bob, 1, Action::VoteReject, None).assert_success();
+
+    // key 0 rewards every voter, key index + 1 only voters of that option
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("1000"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("60"), 0, 1).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("20"), 0, 3).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("40"), 1, 2).assert_success();
+    assert_err!(e.deposit_reward(&tokens.nref, &users.dude, to_yocto("40"), 0, 4), E207_INVALID_INCENTIVE_KEY);
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_eq!(to_yocto("110"), e.get_unclaimed_rewards(&users.alice).get(tokens.nref.account_id().as_str()).unwrap().0);
+    assert_eq!(to_yocto("90"), e.get_unclaimed_rewards(&users.bob).get(tokens.nref.account_id().as_str()).unwrap().0);
+
+    e.claim_reward(&users.alice, 0).assert_success();
+    e.claim_reward(&users.bob, 0).assert_success();
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("110"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    e.claim_reward(&users.alice, 1).assert_success();
+    e.claim_reward(&users.bob, 1).assert_success();
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("110"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("90"))]), e.get_account_info(&users.bob).unwrap().rewards);
+
+    // nobody voted topic3, its incentive goes back to the depositor
+    assert_err!(e.refund_unused_incentive(&users.dude, 0, 1), E209_INCENTIVE_STILL_CLAIMABLE);
+    e.refund_unused_incentive(&users.dude, 0, 3).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.dude), to_yocto("800"));
+}
+
 #[test]
 fn test_withdraw_reward() {
     let e = init_env();
@@ -253,7 +303,7 @@ fn test_withdraw_reward() {
     e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
     e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
 
-    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
     e.skip_time(DAY_SEC);
     e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
     e.ft_mint(&tokens.nref, &users.alice, to_yocto("100"));
@@ -262,16 +312,506 @@ fn test_withdraw_reward() {
     e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
     assert_eq!(HashMap::new(), e.get_account_info(&users.alice).unwrap().rewards);
     e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
 
     assert_err!(e.storage_unregister(&users.alice, 1), E103_STILL_HAS_REWARD);
 
     assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), 0);
     e.withdraw_reward(&users.alice, &tokens.nref, Some(to_yocto("50")));
-    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("50"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("50"))]), e.get_account_info(&users.alice).unwrap().rewards);
     assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("50"));
 
     e.withdraw_reward(&users.alice, &tokens.nref, None);
     assert_eq!(HashMap::new(), e.get_account_info(&users.alice).unwrap().rewards);
     assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("100"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_withdraw_vesting_reward() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.ft_storage_deposit(&users.alice, &tokens.nref);
+    e.deposit_vesting_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0, 10 * DAY_SEC).assert_success();
+    assert_eq!(10 * DAY_SEC, e.get_proposal(0).unwrap().incentive.get(&0).unwrap().vesting_duration_sec);
+
+    // error scene 
+    // 1 : E214_INVALID_VESTING_DURATION
+    assert_err!(e.deposit_reward(&tokens.nref, &users.dude, to_yocto("10"), 0, 0), E214_INVALID_VESTING_DURATION);
+    assert_err!(e.deposit_vesting_reward(&tokens.nref, &users.dude, to_yocto("10"), 0, 0, DAY_SEC), E214_INVALID_VESTING_DURATION);
+    assert_err!(e.deposit_vesting_reward(&tokens.nref, &users.dude, to_yocto("10"), 0, 0, DEFAULT_MAX_LOCKING_DURATION_SEC + 1), E214_INVALID_VESTING_DURATION);
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_reward(&users.alice, 0).assert_success();
+    let vesting_rewards = e.get_account_info(&users.alice).unwrap().vesting_rewards;
+    assert_eq!(1, vesting_rewards.len());
+    assert_eq!(tokens.nref.account_id().to_string(), vesting_rewards[0].token_id);
+    assert_eq!(to_yocto("100"), vesting_rewards[0].amount);
+    assert_eq!(e.get_proposal(0).unwrap().end_at, vesting_rewards[0].start_at);
+    assert_err!(e.storage_unregister(&users.alice, 1), E103_STILL_HAS_REWARD);
+
+    // 2 : E101_INSUFFICIENT_BALANCE, only the vested part can be withdrawn
+    e.skip_time(5 * DAY_SEC);
+    assert_err!(e.withdraw_reward(&users.alice, &tokens.nref, Some(to_yocto("100"))), E101_INSUFFICIENT_BALANCE);
+    let account_info = e.get_account_info(&users.alice).unwrap();
+    let released = account_info.vesting_rewards[0].released;
+    assert!(released >= to_yocto("50") && released < to_yocto("100"));
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), released)]), account_info.rewards);
+
+    e.withdraw_reward(&users.alice, &tokens.nref, None).assert_success();
+    let balance = e.ft_balance_of(&tokens.nref, &users.alice);
+    assert!(balance >= released && balance < to_yocto("100"));
+
+    e.skip_time(5 * DAY_SEC);
+    e.withdraw_reward(&users.alice, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("100"));
+    let account_info = e.get_account_info(&users.alice).unwrap();
+    assert!(account_info.vesting_rewards.is_empty());
+    assert_eq!(HashMap::new(), account_info.rewards);
+}
+
+#[test]
+fn test_withdraw_reward_with_registration() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_reward(&users.alice, 0).assert_success();
+    e.claim_reward(&users.bob, 0).assert_success();
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED
+    assert_err!(e.fund_reward_storage_budget(&users.alice, to_yocto("1")), E002_NOT_ALLOWED);
+    e.fund_reward_storage_budget(&e.owner, to_yocto("1")).assert_success();
+    assert_eq!(to_yocto("1"), e.get_metadata().reward_storage_budget.0);
+
+    // 2 : E310_INSUFFICIENT_STORAGE_BUDGET
+    assert_err!(e.withdraw_reward_storage_budget(&e.owner, to_yocto("2"), 1), E310_INSUFFICIENT_STORAGE_BUDGET);
+    e.withdraw_reward_storage_budget(&e.owner, to_yocto("1") - STORAGE_BALANCE_MIN_BOUND, 1).assert_success();
+    assert_eq!(STORAGE_BALANCE_MIN_BOUND, e.get_metadata().reward_storage_budget.0);
+
+    // alice is not registered on nref, she gets registered by the budget
+    e.withdraw_reward_with_registration(&users.alice, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("50"));
+    assert_eq!(0, e.get_metadata().reward_storage_budget.0);
+    assert!(e.get_account_info(&users.alice).unwrap().rewards.is_empty());
+
+    // budget is used up, bob's transfer fails and the reward is kept
+    e.withdraw_reward_with_registration(&users.bob, &tokens.nref, None).assert_success();
+    assert_eq!(&to_yocto("50"), e.get_account_info(&users.bob).unwrap().rewards.get(tokens.nref.account_id().as_str()).unwrap());
+    e.ft_storage_deposit(&users.bob, &tokens.nref);
+    e.withdraw_reward_with_registration(&users.bob, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), to_yocto("50"));
+}
+
+#[test]
+fn test_claim_rewards_and_withdraw_rewards() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id(), tokens.wnear.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    for _ in 0..3 {
+        e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    }
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.alice, 2, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.ft_mint(&tokens.wnear, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.wnear, &users.dude, to_yocto("100"), 1, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 2, 0).assert_success();
+
+    // not expired yet, nothing claimed
+    let result: ClaimRewardsResult = e.claim_rewards(&users.alice, vec![0, 1]).unwrap_json();
+    assert!(result.claimed_proposal_ids.is_empty());
+    assert!(result.rewards.is_empty());
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    let result: ClaimRewardsResult = e.claim_rewards(&users.alice, vec![0, 1, 0]).unwrap_json();
+    assert_eq!(vec![0, 1], result.claimed_proposal_ids);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100")), (tokens.wnear.account_id().to_string(), to_yocto("100"))]), result.rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100")), (tokens.wnear.account_id().to_string(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert!(e.get_vote_detail(&users.alice).contains_key(&2));
+
+    e.ft_storage_deposit(&users.alice, &tokens.nref);
+    e.ft_storage_deposit(&users.alice, &tokens.wnear);
+
+    // 60 TGas is only enough for one transfer
+    let result: WithdrawRewardsResult = e.withdraw_rewards(&users.alice, vec![&tokens.nref, &tokens.wnear], MAX_GAS.0 / 5).unwrap_json();
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), result.withdrawn);
+    assert_eq!(vec![tokens.wnear.account_id().to_string()], result.remaining_token_ids);
+    assert_eq!(to_yocto("100"), e.ft_balance_of(&tokens.nref, &users.alice));
+
+    let result: WithdrawRewardsResult = e.withdraw_rewards(&users.alice, vec![&tokens.wnear], MAX_GAS.0).unwrap_json();
+    assert_eq!(HashMap::from([(tokens.wnear.account_id().to_string(), to_yocto("100"))]), result.withdrawn);
+    assert!(result.remaining_token_ids.is_empty());
+    assert_eq!(to_yocto("100"), e.ft_balance_of(&tokens.wnear, &users.alice));
+    assert!(e.get_account_info(&users.alice).unwrap().rewards.is_empty());
+}
+
+#[test]
+fn test_keeper_claim() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+    e.modify_keeper_fee_bps(&e.owner, 100).assert_success();
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+
+    // error scene 
+    // 1 : E100_ACC_NOT_REGISTERED
+    assert_err!(e.keeper_claim(&users.charlie, vec![&users.alice]), E100_ACC_NOT_REGISTERED);
+    // 2 : Requires attached deposit of exactly 1 yoctoNEAR
+    assert_err!(e.set_keeper_claim_disabled(&users.bob, true, 0), "Requires attached deposit of exactly 1 yoctoNEAR");
+
+    e.storage_deposit(&users.charlie, &users.charlie, to_yocto("1"));
+    e.set_keeper_claim_disabled(&users.bob, true, 1).assert_success();
+    assert!(e.get_account_info(&users.bob).unwrap().keeper_claim_disabled);
+
+    // not expired yet, nothing claimed
+    let result: KeeperClaimResult = e.keeper_claim(&users.charlie, vec![&users.alice, &users.bob]).unwrap_json();
+    assert!(result.claimed_account_ids.is_empty());
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    let result: KeeperClaimResult = e.keeper_claim(&users.charlie, vec![&users.alice, &users.bob, &users.eve]).unwrap_json();
+    assert_eq!(vec![users.alice.account_id()], result.claimed_account_ids);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("0.5"))]), result.fees);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("49.5"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("0.5"))]), e.get_account_info(&users.charlie).unwrap().rewards);
+    assert!(e.get_vote_detail(&users.alice).is_empty());
+    assert!(e.get_account_info(&users.bob).unwrap().rewards.is_empty());
+    assert!(e.get_vote_detail(&users.bob).contains_key(&0));
+
+    // nothing left to claim
+    let result: KeeperClaimResult = e.keeper_claim(&users.charlie, vec![&users.alice]).unwrap_json();
+    assert!(result.claimed_account_ids.is_empty());
+}
+
+#[test]
+fn test_reward_receiver() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_reward(&users.alice, 0).assert_success();
+
+    // error scene 
+    // 1 : Requires attached deposit of exactly 1 yoctoNEAR
+    assert_err!(e.set_reward_receiver(&users.alice, Some(&users.bob), 0), "Requires attached deposit of exactly 1 yoctoNEAR");
+    // 2 : E100_ACC_NOT_REGISTERED
+    assert_err!(e.set_reward_receiver(&users.eve, Some(&users.bob), 1), E100_ACC_NOT_REGISTERED);
+
+    e.set_reward_receiver(&users.alice, Some(&users.bob), 1).assert_success();
+    assert_eq!(Some(users.bob.account_id()), e.get_account_info(&users.alice).unwrap().reward_receiver);
+
+    // bob is not registered on nref, the reward goes back to alice
+    e.withdraw_reward(&users.alice, &tokens.nref, Some(to_yocto("50"))).assert_success();
+    assert_eq!(&to_yocto("100"), e.get_account_info(&users.alice).unwrap().rewards.get(tokens.nref.account_id().as_str()).unwrap());
+
+    e.ft_storage_deposit(&users.bob, &tokens.nref);
+    e.withdraw_reward(&users.alice, &tokens.nref, Some(to_yocto("50"))).assert_success();
+    assert_eq!(to_yocto("50"), e.ft_balance_of(&tokens.nref, &users.bob));
+
+    e.set_reward_receiver(&users.alice, None, 1).assert_success();
+    assert_eq!(None, e.get_account_info(&users.alice).unwrap().reward_receiver);
+    e.ft_storage_deposit(&users.alice, &tokens.nref);
+    e.claim_and_withdraw_all(&users.alice).assert_success();
+    assert_eq!(to_yocto("50"), e.ft_balance_of(&tokens.nref, &users.alice));
+    assert!(e.get_account_info(&users.alice).unwrap().rewards.is_empty());
+}
+
+#[test]
+fn test_mft_and_near_reward() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let mft_token_id = format!("{}@{}", e.lptoken_contract.account_id(), lpt_id());
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+
+    // error scene 
+    // 1 : E203_INVALID_INCENTIVE_TOKEN
+    assert_err!(e.deposit_mft_reward(&users.bob, to_yocto("50"), 0, 0), E203_INVALID_INCENTIVE_TOKEN);
+    assert_err!(e.deposit_near_incentive(&users.dude, 0, 0, to_yocto("10")), E203_INVALID_INCENTIVE_TOKEN);
+    // 2 : E502_INVALID_TOKEN_ID
+    assert_err!(e.extend_whitelisted_incentive_tokens(&e.owner, vec!["mft.near@"]), E502_INVALID_TOKEN_ID);
+    assert_err!(e.extend_whitelisted_incentive_tokens(&e.owner, vec!["Near"]), E502_INVALID_TOKEN_ID);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![mft_token_id.clone(), "NEAR".to_string()]).assert_success();
+    e.deposit_mft_reward(&users.bob, to_yocto("50"), 0, 0).assert_success();
+    e.deposit_near_incentive(&users.dude, 0, 0, to_yocto("10")).assert_success();
+    assert_eq!(e.mft_balance_of(&users.bob, &lpt_id()), to_yocto("50"));
+    let incentive = e.get_proposal(0).unwrap().incentive.get(&0).unwrap().clone();
+    assert_eq!(vec![mft_token_id.clone(), "NEAR".to_string()], incentive.incentive_token_ids);
+    assert_eq!(vec![to_yocto("50"), to_yocto("10")], incentive.incentive_amounts);
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    e.claim_reward(&users.alice, 0).assert_success();
+    assert_eq!(HashMap::from([(mft_token_id.clone(), to_yocto("50")), ("NEAR".to_string(), to_yocto("10"))]), e.get_account_info(&users.alice).unwrap().rewards);
+
+    // LP shares go back by mft_transfer, NEAR by a transfer
+    e.withdraw_reward_token(&users.alice, &mft_token_id, None).assert_success();
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("150"));
+    let near_balance_before = users.alice.account().unwrap().amount;
+    e.withdraw_reward_token(&users.alice, "NEAR", None).assert_success();
+    assert!(users.alice.account().unwrap().amount > near_balance_before + to_yocto("9.9"));
+    assert_eq!(HashMap::new(), e.get_account_info(&users.alice).unwrap().rewards);
+}
+
+#[test]
+fn test_refund_unused_incentive() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("100"));
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("30"), 0, 1).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("70"), 0, 1).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("20"), 1, 0).assert_success();
+    assert_eq!(2, e.list_incentive_deposits(0, 1, None, None).len());
+    assert!(e.list_incentive_deposits(0, 1, None, None).contains(&IncentiveDeposit {
+        token_id: tokens.nref.account_id().to_string(),
+        depositor_id: users.bob.account_id(),
+        amount: to_yocto("30").into(),
+    }));
+
+    // error scene 
+    // 1 : E409_PROPOSAL_NOT_EXPIRED
+    assert_err!(e.refund_unused_incentive(&users.bob, 0, 1), E409_PROPOSAL_NOT_EXPIRED);
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+
+    // 2 : E209_INCENTIVE_STILL_CLAIMABLE
+    assert_err!(e.refund_unused_incentive(&users.bob, 0, 0), E209_INCENTIVE_STILL_CLAIMABLE);
+    // 3 : E207_INVALID_INCENTIVE_KEY
+    assert_err!(e.refund_unused_incentive(&users.bob, 1, 1), E207_INVALID_INCENTIVE_KEY);
+
+    e.refund_unused_incentive(&users.bob, 0, 1).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), to_yocto("80"));
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.dude), to_yocto("70"));
+    let incentive = e.get_proposal(0).unwrap().incentive.get(&1).unwrap().clone();
+    assert_eq!(incentive.incentive_amounts, incentive.claimed_amounts);
+    assert!(e.list_incentive_deposits(0, 1, None, None).is_empty());
+    // 4 : E210_NOTHING_TO_REFUND
+    assert_err!(e.refund_unused_incentive(&users.bob, 0, 1), E210_NOTHING_TO_REFUND);
+
+    // nobody voted the whole proposal, a failed refund could be retried
+    e.ft_storage_unregister(&tokens.nref, &users.bob);
+    e.refund_unused_incentive(&users.alice, 1, 0).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), 0);
+    assert_eq!(1, e.list_incentive_deposits(1, 0, None, None).len());
+    assert_eq!(0, e.get_proposal(1).unwrap().incentive.get(&0).unwrap().claimed_amounts[0]);
+    e.ft_storage_deposit(&users.bob, &tokens.nref);
+    e.refund_unused_incentive(&users.alice, 1, 0).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), to_yocto("20"));
+    assert!(e.list_incentive_deposits(1, 0, None, None).is_empty());
+
+    // voters still could claim the incentive of voted option
+    e.claim_reward(&users.alice, 0);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
+}
+
+#[test]
+fn test_sweep_incentive_dust() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
+
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VotePoll { poll_id: 1 }, None).assert_success();
+
+    // the odd yocto can't be split between two equal voters
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100") + 1, 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("50"), 1, 0).assert_success();
+
+    // error scene 
+    // 1 : E309_TREASURY_NOT_SET
+    assert_err!(e.sweep_incentive_dust(&users.charlie, 0), E309_TREASURY_NOT_SET);
+    assert_err!(e.set_treasury(&users.alice, &users.eve, 1), E002_NOT_ALLOWED);
+    e.set_treasury(&e.owner, &users.eve, 1).assert_success();
+    assert_eq!(Some(users.eve.account_id()), e.get_metadata().treasury_id);
+    e.ft_storage_deposit(&users.eve, &tokens.nref);
+
+    // 2 : E409_PROPOSAL_NOT_EXPIRED
+    assert_err!(e.sweep_incentive_dust(&users.charlie, 0), E409_PROPOSAL_NOT_EXPIRED);
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+
+    // 3 : E212_REWARD_NOT_ALL_CLAIMED
+    e.claim_reward(&users.alice, 0).assert_success();
+    assert_err!(e.sweep_incentive_dust(&users.charlie, 0), E212_REWARD_NOT_ALL_CLAIMED);
+    e.claim_reward(&users.bob, 0).assert_success();
+    assert_eq!(2, e.get_proposal(0).unwrap().claimed_count);
+
+    // 4 : E213_UNUSED_INCENTIVE_NOT_REFUNDED
+    assert_err!(e.sweep_incentive_dust(&users.charlie, 1), E213_UNUSED_INCENTIVE_NOT_REFUNDED);
+
+    e.sweep_incentive_dust(&users.charlie, 0).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.eve), 1);
+    let proposal = e.get_proposal(0).unwrap();
+    assert!(proposal.is_settled);
+    assert_eq!(proposal.incentive.get(&0).unwrap().incentive_amounts, proposal.incentive.get(&0).unwrap().claimed_amounts);
+
+    // 5 : E211_PROPOSAL_SETTLED
+    assert_err!(e.sweep_incentive_dust(&users.charlie, 0), E211_PROPOSAL_SETTLED);
+
+    // nothing left once unused incentive refunded
+    e.refund_unused_incentive(&users.charlie, 1, 0).assert_success();
+    e.sweep_incentive_dust(&users.charlie, 1).assert_success();
+    assert!(e.get_proposal(1).unwrap().is_settled);
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.eve), 1);
+}
+
+#[test]
+fn test_projected_rewards() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id(), tokens.wnear.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.ft_mint(&tokens.nref, &users.charlie, to_yocto("100"));
+    e.ft_mint(&tokens.wnear, &users.charlie, to_yocto("100"));
+    e.deposit_reward(&tokens.nref, &users.charlie, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.wnear, &users.charlie, to_yocto("100"), 1, 0).assert_success();
+
+    assert!(e.get_projected_rewards(&users.alice).is_empty());
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    let projected = e.get_projected_rewards(&users.alice);
+    assert_eq!(vec![0, 1], projected.iter().map(|item| item.proposal_id).collect::<Vec<_>>());
+    assert_eq!(Some(ProposalStatus::InProgress), projected[0].status);
+    assert_eq!(to_ve_token("200"), projected[0].total_ballots);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), projected[0].rewards);
+    assert_eq!(HashMap::from([(tokens.wnear.account_id().to_string(), to_yocto("100"))]), projected[1].rewards);
+
+    // projection follows the ballots of other voters
+    e.action_proposal(&users.bob, 0, Action::VotePoll { poll_id: 1 }, None).assert_success();
+    let projected = e.get_projected_rewards(&users.alice);
+    assert_eq!(to_ve_token("400"), projected[0].total_ballots);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("50"))]), projected[0].rewards);
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("50"))]), e.get_projected_rewards(&users.bob)[0].rewards);
+
+    // expired proposals are left to get_unclaimed_rewards
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert!(e.get_projected_rewards(&users.alice).is_empty());
+    assert_eq!(to_yocto("50"), e.get_unclaimed_rewards(&users.alice).get(tokens.nref.account_id().as_str()).unwrap().0);
+}
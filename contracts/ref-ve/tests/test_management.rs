@@ -31,6 +31,10 @@ fn test_modify_config(){
     assert_eq!(e.get_config().min_voting_duration_sec, 1000);
     assert_eq!(e.get_config().max_voting_duration_sec, 2000);
 
+    assert_err!(e.modify_keeper_fee_bps(&users.alice, MAX_KEEPER_FEE_BPS + 1), E301_INVALID_RATIO);
+    e.modify_keeper_fee_bps(&users.alice, 100).assert_success();
+    assert_eq!(e.get_metadata().keeper_fee_bps, 100);
+
 }
 
 #[test]
@@ -46,6 +50,7 @@ fn test_return_lpt_lostfound(){
     e.mft_unregister(&lpt_id(), &users.alice);
     e.withdraw_lpt(&users.alice, None).assert_success();
     assert_eq!(to_yocto("100"), e.get_metadata().lostfound.0);
+    assert_eq!(to_yocto("100"), e.list_lpt_lostfound().get(users.alice.account_id().as_str()).unwrap().0);
     
     e.mft_storage_deposit(&lpt_id(), &users.alice);
 
@@ -61,12 +66,13 @@ fn test_return_lpt_lostfound(){
     e.return_lpt_lostfound(&e.owner, &users.alice, to_yocto("100")).assert_success();
     assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("100"));
     assert_eq!(0, e.get_metadata().lostfound.0);
+    assert!(e.list_lpt_lostfound().is_empty());
 
 }
 
 #[test]
 fn test_return_removed_proposal_assets(){
-    let e = init_env();
+    let e = Env::init_with_contract(v0200_ref_ve_wasm_bytes());
     let users = Users::init(&e);
     let tokens = Tokens::init(&e);
 
@@ -90,10 +96,13 @@ fn test_return_removed_proposal_assets(){
     e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 0, 1).assert_success();
     e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 1, 0).assert_success();
 
+    // deposits made before upgrade have no depositor records, so they go to removed proposal assets
+    e.upgrade_contract(&e.owner, ref_ve_wasm_bytes()).assert_success();
+    assert!(e.get_incentive_refunds(&users.alice).is_empty());
     assert_eq!(e.remove_proposal(&users.alice, 0).unwrap_json::<bool>(), true);
-    assert_eq!(to_yocto("200"), e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("200"), e.list_removed_proposal_assets().get(tokens.nref.account_id().as_str()).unwrap().0);
     assert_eq!(e.remove_proposal(&users.alice, 1).unwrap_json::<bool>(), true);
-    assert_eq!(to_yocto("300"), e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("300"), e.list_removed_proposal_assets().get(tokens.nref.account_id().as_str()).unwrap().0);
 
 
     // error scene 
@@ -106,17 +115,33 @@ fn test_return_removed_proposal_assets(){
     //success
     e.return_removed_proposal_assets(&e.owner, &users.alice, &tokens.nref, to_yocto("200")).assert_success();
     assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("1900"));
-    assert_eq!(to_yocto("100"), e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("100"), e.list_removed_proposal_assets().get(tokens.nref.account_id().as_str()).unwrap().0);
 
     e.ft_storage_unregister(&tokens.nref, &users.alice);
 
     e.return_removed_proposal_assets(&e.owner, &users.alice, &tokens.nref, to_yocto("100")).assert_success();
     assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), 0);
-    assert_eq!(to_yocto("100"), e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("100"), e.list_removed_proposal_assets().get(tokens.nref.account_id().as_str()).unwrap().0);
 
     e.ft_storage_deposit(&users.alice, &tokens.nref);
 
     e.return_removed_proposal_assets(&e.owner, &users.alice, &tokens.nref, to_yocto("100")).assert_success();
-    assert_eq!(0, e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(0, e.list_removed_proposal_assets().get(tokens.nref.account_id().as_str()).unwrap().0);
     assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("100"));
 }
+
+#[test]
+fn test_return_reward_lostfound(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    assert!(e.list_reward_lostfound(None, None).is_empty());
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED
+    assert_err!(e.return_reward_lostfound(&e.near, &users.alice, &tokens.nref, to_yocto("100")), E002_NOT_ALLOWED);
+
+    // 2 : E101_INSUFFICIENT_BALANCE
+    assert_err!(e.return_reward_lostfound(&e.owner, &users.alice, &tokens.nref, to_yocto("100")), E101_INSUFFICIENT_BALANCE);
+}
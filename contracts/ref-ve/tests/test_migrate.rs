@@ -1,5 +1,6 @@
 mod setup;
 use crate::setup::*;
+use std::collections::HashMap;
 
 #[test]
 fn test_update(){
@@ -15,6 +16,150 @@ fn test_update(){
         e.ve_contract.contract.get_config()
     ).unwrap_json_value());
     e.upgrade_contract(&e.owner, ref_ve_wasm_bytes()).assert_success();
-    assert_eq!(e.get_metadata().version, "0.2.3".to_string());
+    assert_eq!(e.get_metadata().version, "0.3.0".to_string());
     println!("{:?}", e.get_config());
-}
\ No newline at end of file
+}
+#[test]
+fn test_update_from_v0200(){
+    let e = Env::init_with_contract(v0200_ref_ve_wasm_bytes());
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+
+    e.create_proposal(&users.alice, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + 2 * DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("300"));
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 1).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+
+    e.upgrade_contract(&e.owner, ref_ve_wasm_bytes()).assert_success();
+    assert_eq!(e.get_metadata().version, "0.3.0".to_string());
+
+    // proposals stored by 0.2.3 keep their incentives
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!(to_yocto("100"), proposal.incentive.get(&0).unwrap().incentive_amounts[0]);
+    assert_eq!(1, proposal.participants);
+    assert!(e.list_incentive_deposits(0, 1, None, None).is_empty());
+
+    // proposals stored by 0.2.3 are indexed by operators in batches
+    assert!(e.list_proposals_by_filter(ProposalFilter { proposer: Some(users.alice.account_id()), ..Default::default() }, None, None).proposals.is_empty());
+    assert_err!(e.index_proposals(&users.alice, 0, None), E002_NOT_ALLOWED);
+    assert_eq!(1, e.index_proposals(&e.owner, 1, Some(1)).unwrap_json::<u32>());
+    assert_eq!(1, e.index_proposals(&e.owner, 0, None).unwrap_json::<u32>());
+    assert_eq!(0, e.index_proposals(&e.owner, 0, None).unwrap_json::<u32>());
+    let page = e.list_proposals_by_filter(ProposalFilter { proposer: Some(users.alice.account_id()), ..Default::default() }, None, None);
+    assert_eq!(vec![1, 0], page.proposals.iter().map(|proposal| proposal.id).collect::<Vec<_>>());
+    let page = e.list_proposals_by_filter(ProposalFilter { kind: Some("Poll".to_string()), ..Default::default() }, None, None);
+    assert_eq!(vec![1], page.proposals.iter().map(|proposal| proposal.id).collect::<Vec<_>>());
+
+    // accounts registered by 0.2.3 are indexed by operators in batches
+    assert_eq!(0, e.get_metadata().indexed_account_count.0);
+    assert!(e.list_accounts(None, None).is_empty());
+    assert_eq!(1, e.index_accounts(&e.owner, vec![users.alice.account_id(), users.charlie.account_id()]).unwrap_json::<u32>());
+    assert_eq!(0, e.index_accounts(&e.owner, vec![users.alice.account_id()]).unwrap_json::<u32>());
+    assert_eq!(e.get_metadata().account_count, e.get_metadata().indexed_account_count);
+    assert_eq!(vec![users.alice.account_id()], e.list_accounts(None, None).into_iter().map(|info| info.account_id).collect::<Vec<_>>());
+
+    // deposit to a migrated proposal is recorded
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 1, 0).assert_success();
+    assert_eq!(vec![IncentiveDeposit {
+        token_id: tokens.nref.account_id().to_string(),
+        depositor_id: users.bob.account_id(),
+        amount: to_yocto("100").into(),
+    }], e.list_incentive_deposits(1, 0, None, None));
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC + DAY_SEC);
+    e.claim_reward(&users.alice, 0).assert_success();
+    assert_eq!(HashMap::from([(tokens.nref.account_id().to_string(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
+
+    // deposits made before upgrade have no depositor records
+    assert_err!(e.refund_unused_incentive(&users.bob, 0, 1), E210_NOTHING_TO_REFUND);
+    e.refund_unused_incentive(&users.bob, 1, 0).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), to_yocto("100"));
+
+    // unused deposits made before upgrade are not dust, they go to removed_proposal_assets
+    e.set_treasury(&e.owner, &users.eve, 1).assert_success();
+    e.ft_storage_deposit(&users.eve, &tokens.nref);
+    e.sweep_incentive_dust(&users.charlie, 0).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.eve), 0);
+    assert_eq!(to_yocto("100"), e.list_removed_proposal_assets().get(&tokens.nref.account_id().to_string()).unwrap().0);
+    assert!(e.get_proposal(0).unwrap().is_settled);
+}
+
+#[test]
+fn test_sweep_incentive_dust_after_update(){
+    let e = Env::init_with_contract(v0200_ref_ve_wasm_bytes());
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VotePoll { poll_id: 1 }, None).assert_success();
+    e.ft_mint(&tokens.nref, &users.charlie, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.charlie, to_yocto("100") + 1, 0, 0).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+
+    // claimed before upgrade, which can't be counted
+    e.claim_reward(&users.alice, 0).assert_success();
+
+    e.upgrade_contract(&e.owner, ref_ve_wasm_bytes()).assert_success();
+    let proposal = e.get_proposal(0).unwrap();
+    assert!(proposal.has_untracked_claims);
+    assert_eq!(0, proposal.claimed_count);
+
+    e.set_treasury(&e.owner, &users.eve, 1).assert_success();
+    e.ft_storage_deposit(&users.eve, &tokens.nref);
+    assert_err!(e.sweep_incentive_dust(&users.charlie, 0), E212_REWARD_NOT_ALL_CLAIMED);
+
+    e.claim_reward(&users.bob, 0).assert_success();
+    e.sweep_incentive_dust(&users.charlie, 0).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.eve), 1);
+    assert!(e.get_proposal(0).unwrap().is_settled);
+}
+
+#[test]
+fn test_storage_after_update_from_v0200(){
+    let e = Env::init_with_contract(v0200_ref_ve_wasm_bytes());
+    let users = Users::init(&e);
+
+    // dude registered with only the minimum storage balance
+    e.storage_deposit(&users.dude, &users.dude, STORAGE_BALANCE_MIN_BOUND).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.dude, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.dude, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.dude, 0, Action::VoteApprove, None).assert_success();
+
+    e.upgrade_contract(&e.owner, ref_ve_wasm_bytes()).assert_success();
+
+    // the migrated record is the baseline, dude's own calls still work
+    assert_eq!(STORAGE_BALANCE_MIN_BOUND, e.storage_balance_of(&users.dude).unwrap().total.0);
+    assert_eq!(0, e.storage_balance_of(&users.dude).unwrap().available.0);
+    e.action_proposal(&users.dude, 1, Action::VoteReject, None).assert_success();
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    e.claim_reward(&users.dude, 0).assert_success();
+    e.claim_reward(&users.dude, 1).assert_success();
+    e.withdraw_lpt(&users.dude, None).assert_success();
+    assert_eq!(0, e.get_account_info(&users.dude).unwrap().lpt_amount);
+}
@@ -64,3 +64,35 @@ fn test_operators(){
     assert_eq!(e.get_metadata().operators, vec![]);
 }
 
+#[test]
+fn test_guardians(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    // error scene 
+    // 1 : extend with Requires attached deposit of exactly 1 yoctoNEAR
+    assert_err!(
+        e.extend_guardians(&e.owner, vec![], 0),
+        "Requires attached deposit of exactly 1 yoctoNEAR"
+    );
+
+    // 2 : extend with E002_NOT_ALLOWED
+    assert_err!(
+        e.extend_guardians(&users.alice, vec![], 1),
+        E002_NOT_ALLOWED
+    );
+
+    // 3 : remove with E002_NOT_ALLOWED
+    assert_err!(
+        e.remove_guardians(&users.alice, vec![], 1),
+        E002_NOT_ALLOWED
+    );
+
+    // success
+    assert_eq!(e.get_metadata().guardians, vec![]);
+    e.extend_guardians(&e.owner, vec![&users.bob], 1).assert_success();
+    assert_eq!(e.get_metadata().guardians, vec![users.bob.account_id()]);
+    assert_eq!(e.get_metadata().operators, vec![]);
+    e.remove_guardians(&e.owner, vec![&users.bob], 1).assert_success();
+    assert_eq!(e.get_metadata().guardians, vec![]);
+}
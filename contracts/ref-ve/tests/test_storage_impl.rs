@@ -8,15 +8,16 @@ fn test_storage_impl() {
 
     assert_err!(e.storage_deposit(&users.alice, &users.alice, to_yocto("0.000125")), E102_INSUFFICIENT_STORAGE);
 
-    assert_err!(e.storage_withdraw(&users.alice, 1), E101_INSUFFICIENT_BALANCE);
+    assert_err!(e.storage_withdraw(&users.alice, None, 1), E100_ACC_NOT_REGISTERED);
 
     // alice register
     assert_eq!(e.get_metadata().account_count.0, 0);
     e.storage_deposit(&users.alice, &users.alice, to_yocto("0.00125")).assert_success();
     assert_eq!(e.get_metadata().account_count.0, 1);
-    // alice register again
+    // alice register again, which tops up her storage balance
     e.storage_deposit(&users.alice, &users.alice, to_yocto("0.00125")).assert_success();
     assert_eq!(e.get_metadata().account_count.0, 1);
+    assert_eq!(to_yocto("0.0025"), e.storage_balance_of(&users.alice).unwrap().total.0);
     assert_eq!(e.get_account_info(&users.alice).unwrap().sponsor_id, users.alice.account_id());
 
     // alice help bob register
@@ -31,8 +32,8 @@ fn test_storage_impl() {
     let user_balance_before = users.alice.account().unwrap().amount;
     assert_eq!(e.storage_unregister(&users.alice, 1).unwrap_json::<bool>(), true);
     let user_balance_after = users.alice.account().unwrap().amount;
-    assert!(user_balance_after > user_balance_before);
-    assert!(user_balance_after - user_balance_before < to_yocto("0.00125"));
+    assert!(user_balance_after - user_balance_before > to_yocto("0.00125"));
+    assert!(user_balance_after - user_balance_before < to_yocto("0.0025"));
     assert_eq!(e.get_metadata().account_count.0, 1);
 
     // bob unregister
@@ -72,4 +73,78 @@ fn test_storage_impl() {
     let user_balance_after = users.dude.account().unwrap().amount;
     assert!(user_balance_after < user_balance_before);
     assert_eq!(e.get_metadata().account_count.0, 0);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_storage_withdraw() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.storage_deposit(&users.alice, &users.bob, to_yocto("1")).assert_success();
+    let storage_balance = e.storage_balance_of(&users.bob).unwrap();
+    assert_eq!(to_yocto("1"), storage_balance.total.0);
+    assert_eq!(to_yocto("1") - STORAGE_BALANCE_MIN_BOUND, storage_balance.available.0);
+
+    // registration only keeps the minimum
+    users.alice.function_call(
+        e.ve_contract.contract.storage_deposit(Some(users.dude.account_id()), Some(true)),
+        MAX_GAS.0,
+        to_yocto("1"),
+    ).assert_success();
+    let storage_balance = e.storage_balance_of(&users.dude).unwrap();
+    assert_eq!(STORAGE_BALANCE_MIN_BOUND, storage_balance.total.0);
+    assert_eq!(0, storage_balance.available.0);
+
+    // error scene 
+    // 1 : Requires attached deposit of exactly 1 yoctoNEAR
+    assert_err!(e.storage_withdraw(&users.bob, None, 0), "Requires attached deposit of exactly 1 yoctoNEAR");
+    // 2 : E101_INSUFFICIENT_BALANCE
+    assert_err!(e.storage_withdraw(&users.bob, Some(to_yocto("1")), 1), E101_INSUFFICIENT_BALANCE);
+
+    let user_balance_before = users.bob.account().unwrap().amount;
+    e.storage_withdraw(&users.bob, Some(to_yocto("0.5")), 1).assert_success();
+    assert!(users.bob.account().unwrap().amount > user_balance_before);
+    assert_eq!(to_yocto("0.5"), e.storage_balance_of(&users.bob).unwrap().total.0);
+
+    // the registration fee covers the first growth of bob's storage
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.storage_deposit(&users.alice, &users.charlie, to_yocto("1")).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.charlie.account_id()]).assert_success();
+    e.storage_withdraw(&users.bob, None, 1).assert_success();
+    assert_eq!(STORAGE_BALANCE_MIN_BOUND, e.storage_balance_of(&users.bob).unwrap().total.0);
+    let mut proposal_id = 0;
+    e.create_proposal(&users.charlie, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.bob, proposal_id, Action::VoteApprove, None).assert_success();
+
+    // bob can't vote any more once the growth is beyond the storage balance
+    loop {
+        proposal_id += 1;
+        e.create_proposal(&users.charlie, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+        e.skip_time(DAY_SEC);
+        let result = e.action_proposal(&users.bob, proposal_id, Action::VoteApprove, None);
+        if !result.is_ok() {
+            assert_err!(result, E102_INSUFFICIENT_STORAGE);
+            break;
+        }
+        assert!(proposal_id < 20);
+    }
+    e.storage_deposit(&users.bob, &users.bob, to_yocto("0.01")).assert_success();
+    e.action_proposal(&users.bob, proposal_id, Action::VoteApprove, None).assert_success();
+    assert!(e.storage_balance_of(&users.bob).unwrap().available.0 < to_yocto("0.01"));
+
+    // withdrawing lpt is never held back by storage
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    for id in 0..=proposal_id {
+        e.claim_reward(&users.bob, id).assert_success();
+    }
+    e.storage_withdraw(&users.bob, None, 1).assert_success();
+    e.withdraw_lpt(&users.bob, None).assert_success();
+
+    // the vote history must be pruned before unregistering
+    assert_err!(e.storage_unregister(&users.bob, 1), E108_STILL_HAS_VOTE_HISTORY);
+    e.prune_proposal_history(&users.bob, proposal_id + 1, None, None, 1).assert_success();
+    e.storage_unregister(&users.bob, 1).assert_success();
+}
@@ -65,9 +65,9 @@ fn test_deposit_reward() {
     e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
     e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
 
-    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();//, Some((tokens.nref.account_id(), IncentiveType::Evenly))
-    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
-    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();//, Some((tokens.nref.account_id(), IncentiveType::Evenly))
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, PROPOSAL_STORAGE_DEPOSIT).assert_success();
     e.skip_time(DAY_SEC);
     e.action_proposal(&users.alice, 2, Action::VotePoll { poll_id: 0 }, None).assert_success();
     e.ft_mint(&tokens.nref, &users.alice, to_yocto("2000"));
@@ -76,8 +76,8 @@ fn test_deposit_reward() {
 
     // 1 : E207_INVALID_INCENTIVE_KEY
     assert_err!(e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 0, 5), E207_INVALID_INCENTIVE_KEY);
-    assert_err!(e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 1, 1), E207_INVALID_INCENTIVE_KEY);
-    assert_err!(e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 2, 1), E207_INVALID_INCENTIVE_KEY);
+    assert_err!(e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 1, 4), E207_INVALID_INCENTIVE_KEY);
+    assert_err!(e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 2, 3), E207_INVALID_INCENTIVE_KEY);
 
     // success 
     e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 0, 0).assert_success();